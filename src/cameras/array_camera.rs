@@ -0,0 +1,150 @@
+#[cfg(feature = "serde")]
+use serde_json::{Map, Value};
+use super::camera::{Camera, HasCamera};
+use super::perspective_camera::PerspectiveCamera;
+use super::super::core::object3d::{Object3D, HasObject3D};
+use super::super::math::matrix4::Matrix4;
+use super::super::math::vector4::Vector4;
+
+#[derive(Debug, Clone)]
+pub struct SubCamera {
+	pub camera: PerspectiveCamera,
+	// a rectangle of the render target in pixels as (x, y, width, height),
+	// with the origin at the bottom left
+	pub viewport: Vector4,
+}
+
+// renders several views in one pass, each sub-camera into its own viewport. the
+// sub-cameras are children of the rig and follow its world matrix
+#[derive(Debug, Clone)]
+pub struct ArrayCamera {
+	camera: PerspectiveCamera,
+	cameras: Vec<SubCamera>,
+}
+
+impl HasCamera for ArrayCamera {
+	fn get_camera(&self) -> &Camera {
+		self.camera.get_camera()
+	}
+
+	fn get_camera_mut(&mut self) -> &mut Camera {
+		self.camera.get_camera_mut()
+	}
+}
+
+impl HasObject3D for ArrayCamera {
+	fn get_object3d(&self) -> &Object3D {
+		self.camera.get_object3d()
+	}
+
+	fn get_object3d_mut(&mut self) -> &mut Object3D {
+		self.camera.get_object3d_mut()
+	}
+
+	fn get_type(&self) -> &'static str {
+		"ArrayCamera"
+	}
+
+	fn update_matrix_world_from(&mut self, parent_matrix_world: Option<&Matrix4>, force: bool) -> bool {
+		let updated = self.camera.update_matrix_world_from(parent_matrix_world, force);
+		let matrix_world = *self.camera.get_object3d().get_matrix_world();
+
+		for sub_camera in self.cameras.iter_mut() {
+			sub_camera.camera.update_matrix_world_from(Some(&matrix_world), updated);
+		}
+
+		updated
+	}
+
+	fn update_world_matrix_from(&mut self, parent_matrix_world: Option<&Matrix4>) {
+		self.camera.update_world_matrix_from(parent_matrix_world);
+		let matrix_world = *self.camera.get_object3d().get_matrix_world();
+
+		for sub_camera in self.cameras.iter_mut() {
+			sub_camera.camera.update_world_matrix_from(Some(&matrix_world));
+		}
+	}
+
+	// written like the perspective camera it wraps, as three.js does, plus the
+	// sub-cameras with their viewports as (x, y, width, height)
+	#[cfg(feature = "serde")]
+	fn write_json_fields(&self, object: &mut Map<String, Value>) {
+		self.camera.write_json_fields(object);
+
+		let cameras = self.cameras.iter().map(|sub_camera| {
+			let mut camera = sub_camera.camera.get_object3d().to_json_object();
+			camera.insert("type".to_string(), Value::from(sub_camera.camera.get_type()));
+			sub_camera.camera.write_json_fields(&mut camera);
+
+			let viewport = &sub_camera.viewport;
+			camera.insert("viewport".to_string(), Value::from(vec![viewport.x, viewport.y, viewport.z, viewport.w]));
+			Value::Object(camera)
+		}).collect::<Vec<_>>();
+		object.insert("cameras".to_string(), Value::Array(cameras));
+	}
+}
+
+impl ArrayCamera {
+	pub fn new(cameras: Vec<SubCamera>) -> ArrayCamera {
+		ArrayCamera {
+			camera: PerspectiveCamera::new(50.0, 1.0, 0.1, 2000.0),
+			cameras,
+		}
+	}
+
+	pub fn get_perspective_camera(&self) -> &PerspectiveCamera {
+		&self.camera
+	}
+
+	pub fn get_perspective_camera_mut(&mut self) -> &mut PerspectiveCamera {
+		&mut self.camera
+	}
+
+	pub fn get_cameras(&self) -> &Vec<SubCamera> {
+		&self.cameras
+	}
+
+	pub fn get_cameras_mut(&mut self) -> &mut Vec<SubCamera> {
+		&mut self.cameras
+	}
+
+	pub fn add(&mut self, camera: PerspectiveCamera, viewport: Vector4) {
+		self.cameras.push(SubCamera {
+			camera,
+			viewport,
+		});
+	}
+
+	pub fn update_matrix_world(&mut self, force: bool) {
+		self.update_matrix_world_from(None, force);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ArrayCamera;
+	use super::super::perspective_camera::PerspectiveCamera;
+	use super::super::super::core::object3d::HasObject3D;
+	use super::super::super::math::vector3::Vector3;
+	use super::super::super::math::vector4::Vector4;
+
+	#[test]
+	fn sub_cameras_follow_the_rig() {
+		let mut array_camera = ArrayCamera::new(vec![]);
+		for i in 0..2 {
+			let mut camera = PerspectiveCamera::new(40.0, 1.0, 0.1, 10.0);
+			camera.get_object3d_mut().get_position_mut().set(i as f32, 0.0, 0.0);
+			array_camera.add(camera, Vector4 { x: i as f32 * 256.0, y: 0.0, z: 256.0, w: 256.0 });
+		}
+
+		array_camera.get_object3d_mut().get_position_mut().set(0.0, 0.0, 5.0);
+		array_camera.update_matrix_world(false);
+
+		let sub_camera = &array_camera.get_cameras()[ 1 ];
+		assert_eq!(sub_camera.viewport.x, 256.0);
+
+		let mut position = Vector3::new();
+		position.set_from_matrix_position(sub_camera.camera.get_object3d().get_matrix_world());
+		assert!(position.equals(&Vector3 { x: 1.0, y: 0.0, z: 5.0 }));
+	}
+}
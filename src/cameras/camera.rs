@@ -1,16 +1,116 @@
+#[cfg(feature = "serde")]
+use serde_json::{Map, Value};
 use super::super::math::matrix4::Matrix4;
+use super::super::math::vector3::Vector3;
+use super::super::core::object3d::{Object3D, HasObject3D};
+use super::super::errors::SingularMatrixError;
 
-#[derive(Debug, Clone, Copy)]
+pub trait HasCamera {
+	fn get_camera(&self) -> &Camera;
+	fn get_camera_mut(&mut self) -> &mut Camera;
+}
+
+// cameras hold their Object3D inside Camera, so every camera type forwards the same way
+macro_rules! impl_camera_object3d {
+	($t:ident) => {
+		impl HasObject3D for $t {
+			fn get_object3d(&self) -> &Object3D {
+				self.get_camera().get_object3d()
+			}
+
+			fn get_object3d_mut(&mut self) -> &mut Object3D {
+				self.get_camera_mut().get_object3d_mut()
+			}
+
+			fn get_type(&self) -> &'static str {
+				stringify!($t)
+			}
+
+			fn update_matrix_world_from(&mut self, parent_matrix_world: Option<&Matrix4>, force: bool) -> bool {
+				self.get_camera_mut().update_matrix_world_from(parent_matrix_world, force)
+			}
+
+			fn update_world_matrix_from(&mut self, parent_matrix_world: Option<&Matrix4>) {
+				self.get_camera_mut().update_world_matrix_from(parent_matrix_world);
+			}
+
+			#[cfg(feature = "serde")]
+			fn write_json_fields(&self, object: &mut ::serde_json::Map<String, ::serde_json::Value>) {
+				self.write_projection_json(object);
+			}
+		}
+	}
+}
+
+// a sub-rectangle of a larger render, for tiled or multi-monitor setups
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct View {
+	pub full_width: f32,
+	pub full_height: f32,
+	pub offset_x: f32,
+	pub offset_y: f32,
+	pub width: f32,
+	pub height: f32,
+}
+
+#[cfg(feature = "serde")]
+impl View {
+	// the "view" entry of a camera's JSON object
+	pub fn to_json(&self) -> Value {
+		let mut view = Map::new();
+		view.insert("enabled".to_string(), Value::from(true));
+		view.insert("fullWidth".to_string(), Value::from(self.full_width));
+		view.insert("fullHeight".to_string(), Value::from(self.full_height));
+		view.insert("offsetX".to_string(), Value::from(self.offset_x));
+		view.insert("offsetY".to_string(), Value::from(self.offset_y));
+		view.insert("width".to_string(), Value::from(self.width));
+		view.insert("height".to_string(), Value::from(self.height));
+		Value::Object(view)
+	}
+}
+
+#[derive(Debug, Clone)]
 pub struct Camera {
+	object3d: Object3D,
 	pub matrix_world_inverse: Matrix4,
 	pub projection_matrix: Matrix4,
+	matrix_world_inverse_error: Option<SingularMatrixError>,
+}
+
+impl HasObject3D for Camera {
+	fn get_object3d(&self) -> &Object3D {
+		&self.object3d
+	}
+
+	fn get_object3d_mut(&mut self) -> &mut Object3D {
+		&mut self.object3d
+	}
+
+	fn get_type(&self) -> &'static str {
+		"Camera"
+	}
+
+	fn update_matrix_world_from(&mut self, parent_matrix_world: Option<&Matrix4>, force: bool) -> bool {
+		let updated = self.object3d.update_matrix_world_from(parent_matrix_world, force);
+		if updated {
+			self.update_matrix_world_inverse();
+		}
+		updated
+	}
+
+	fn update_world_matrix_from(&mut self, parent_matrix_world: Option<&Matrix4>) {
+		self.object3d.update_world_matrix_from(parent_matrix_world);
+		self.update_matrix_world_inverse();
+	}
 }
 
 impl Camera {
 	pub fn new() -> Camera {
 		Camera {
+			object3d: Object3D::new(),
 			matrix_world_inverse: Matrix4::new(),
 			projection_matrix: Matrix4::new(),
+			matrix_world_inverse_error: None,
 		}
 	}
 
@@ -18,7 +118,129 @@ impl Camera {
 		&self.matrix_world_inverse
 	}
 
+	// the view matrix, or the error if the current world matrix couldn't be inverted
+	pub fn try_matrix_world_inverse(&self) -> Result<&Matrix4, SingularMatrixError> {
+		match self.matrix_world_inverse_error {
+			Some(err) => Err(err),
+			None => Ok(&self.matrix_world_inverse),
+		}
+	}
+
 	pub fn get_projection_matrix(&self) -> &Matrix4 {
 		&self.projection_matrix
 	}
-}
\ No newline at end of file
+
+	// for cameras outside a scene, which have no parent to inherit a world matrix from
+	pub fn update_matrix_world(&mut self, force: bool) {
+		self.update_matrix_world_from(None, force);
+	}
+
+	// a world matrix that can't be inverted (e.g. a zero scale) keeps the last good view matrix
+	// and records the error for try_matrix_world_inverse
+	fn update_matrix_world_inverse(&mut self) {
+		match self.object3d.get_matrix_world().try_inverse() {
+			Ok(inverse) => {
+				self.matrix_world_inverse = inverse;
+				self.matrix_world_inverse_error = None;
+			}
+			Err(err) => self.matrix_world_inverse_error = Some(err),
+		}
+	}
+
+	pub fn get_world_direction(&self, target: &mut Vector3) {
+		let e = self.object3d.get_matrix_world().get_elements();
+		target.set(- e[ 8 ], - e[ 9 ], - e[ 10 ]);
+		target.normalize();
+	}
+
+	// unlike other objects, cameras look down their local negative z axis
+	pub fn look_at(&mut self, target: &Vector3) {
+		let mut m1 = Matrix4::new();
+		m1.look_at(self.object3d.get_position(), target, self.object3d.get_up());
+		self.object3d.set_rotation_from_matrix(&m1);
+	}
+}
+
+impl Default for Camera {
+	fn default() -> Camera {
+		Camera::new()
+	}
+}
+
+impl HasCamera for Camera {
+	fn get_camera(&self) -> &Camera {
+		self
+	}
+
+	fn get_camera_mut(&mut self) -> &mut Camera {
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Camera;
+	use super::super::super::core::object3d::{Object3D, HasObject3D};
+	use super::super::super::math::vector3::Vector3;
+	use super::super::super::scenes::scene::Scene;
+
+	#[test]
+	fn view_matrix_follows_world_matrix() {
+		let mut scene = Scene::new();
+		let parent = scene.add(Object3D::new());
+		scene.get_object3d_mut(parent).unwrap().get_position_mut().set(5.0, 0.0, 0.0);
+
+		let mut camera = Camera::new();
+		camera.get_object3d_mut().get_position_mut().set(0.0, 0.0, 10.0);
+		let camera = scene.add_to(parent, camera);
+
+		let root = scene.get_root();
+		scene.update_matrix_world(root, false);
+
+		let camera = scene.get_as::<Camera>(camera).unwrap();
+		let mut v = Vector3 { x: 5.0, y: 0.0, z: 0.0 };
+		v.apply_matrix4(camera.get_matrix_world_inverse());
+		assert!(v.equals(&Vector3 { x: 0.0, y: 0.0, z: -10.0 }));
+
+		let mut direction = Vector3::new();
+		camera.get_world_direction(&mut direction);
+		assert!(direction.equals(&Vector3 { x: 0.0, y: 0.0, z: -1.0 }));
+	}
+
+	#[test]
+	fn look_at_points_negative_z_at_target() {
+		let mut camera = Camera::new();
+		camera.get_object3d_mut().get_position_mut().set(0.0, 0.0, 10.0);
+		camera.look_at(&Vector3 { x: 3.0, y: 0.0, z: 10.0 });
+		camera.update_matrix_world(false);
+
+		let mut direction = Vector3::new();
+		camera.get_world_direction(&mut direction);
+		assert!((direction.x - 1.0).abs() < 1e-6);
+
+		let mut v = Vector3 { x: 3.0, y: 0.0, z: 10.0 };
+		v.apply_matrix4(camera.get_matrix_world_inverse());
+		assert!((v.z + 3.0).abs() < 1e-5);
+	}
+
+	#[test]
+	fn singular_world_matrix_fails_projection() {
+		let mut camera = Camera::new();
+		camera.get_object3d_mut().get_position_mut().set(0.0, 0.0, 10.0);
+		camera.update_matrix_world(false);
+		let last_good = camera.get_matrix_world_inverse().elements;
+
+		camera.get_object3d_mut().get_scale_mut().set(1.0, 0.0, 1.0);
+		camera.update_matrix_world(false);
+		assert!(camera.try_matrix_world_inverse().is_err());
+		assert_eq!(camera.get_matrix_world_inverse().elements, last_good);
+
+		let mut v: Vector3 = Vector3::new();
+		assert!(v.project(&camera).is_err());
+		assert!(v.unproject(&camera).is_err());
+
+		camera.get_object3d_mut().get_scale_mut().set(1.0, 1.0, 1.0);
+		camera.update_matrix_world(false);
+		assert!(v.project(&camera).is_ok());
+	}
+}
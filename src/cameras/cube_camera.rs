@@ -0,0 +1,126 @@
+#[cfg(feature = "serde")]
+use serde_json::{Map, Value};
+use super::camera::HasCamera;
+use super::perspective_camera::PerspectiveCamera;
+use super::super::core::object3d::{Object3D, HasObject3D};
+use super::super::math::matrix4::Matrix4;
+use super::super::math::vector3::Vector3;
+
+// renders the six faces of a cube map from one point. the cameras are ordered
+// +X, -X, +Y, -Y, +Z, -Z and follow the rig's world matrix as its children
+#[derive(Debug, Clone)]
+pub struct CubeCamera {
+	object3d: Object3D,
+	cameras: [PerspectiveCamera; 6],
+}
+
+impl HasObject3D for CubeCamera {
+	fn get_object3d(&self) -> &Object3D {
+		&self.object3d
+	}
+
+	fn get_object3d_mut(&mut self) -> &mut Object3D {
+		&mut self.object3d
+	}
+
+	fn get_type(&self) -> &'static str {
+		"CubeCamera"
+	}
+
+	fn update_matrix_world_from(&mut self, parent_matrix_world: Option<&Matrix4>, force: bool) -> bool {
+		let updated = self.object3d.update_matrix_world_from(parent_matrix_world, force);
+
+		for camera in self.cameras.iter_mut() {
+			camera.update_matrix_world_from(Some(self.object3d.get_matrix_world()), updated);
+		}
+
+		updated
+	}
+
+	fn update_world_matrix_from(&mut self, parent_matrix_world: Option<&Matrix4>) {
+		self.object3d.update_world_matrix_from(parent_matrix_world);
+
+		for camera in self.cameras.iter_mut() {
+			camera.update_world_matrix_from(Some(self.object3d.get_matrix_world()));
+		}
+	}
+
+	// the faces are rebuilt from near and far, so those are all that's written
+	#[cfg(feature = "serde")]
+	fn write_json_fields(&self, object: &mut Map<String, Value>) {
+		object.insert("near".to_string(), Value::from(self.cameras[ 0 ].near));
+		object.insert("far".to_string(), Value::from(self.cameras[ 0 ].far));
+	}
+}
+
+impl CubeCamera {
+	pub fn new(near: f32, far: f32) -> CubeCamera {
+		let face = |up: Vector3, target: Vector3| {
+			let mut camera = PerspectiveCamera::new(90.0, 1.0, near, far);
+			camera.get_object3d_mut().get_up_mut().copy(&up);
+			camera.get_camera_mut().look_at(&target);
+			camera
+		};
+
+		CubeCamera {
+			object3d: Object3D::new(),
+			cameras: [
+				face(Vector3 { x: 0.0, y: - 1.0, z: 0.0 }, Vector3 { x: 1.0, y: 0.0, z: 0.0 }),
+				face(Vector3 { x: 0.0, y: - 1.0, z: 0.0 }, Vector3 { x: - 1.0, y: 0.0, z: 0.0 }),
+				face(Vector3 { x: 0.0, y: 0.0, z: 1.0 }, Vector3 { x: 0.0, y: 1.0, z: 0.0 }),
+				face(Vector3 { x: 0.0, y: 0.0, z: - 1.0 }, Vector3 { x: 0.0, y: - 1.0, z: 0.0 }),
+				face(Vector3 { x: 0.0, y: - 1.0, z: 0.0 }, Vector3 { x: 0.0, y: 0.0, z: 1.0 }),
+				face(Vector3 { x: 0.0, y: - 1.0, z: 0.0 }, Vector3 { x: 0.0, y: 0.0, z: - 1.0 }),
+			],
+		}
+	}
+
+	pub fn get_cameras(&self) -> &[PerspectiveCamera; 6] {
+		&self.cameras
+	}
+
+	pub fn get_cameras_mut(&mut self) -> &mut [PerspectiveCamera; 6] {
+		&mut self.cameras
+	}
+
+	pub fn update_matrix_world(&mut self, force: bool) {
+		self.update_matrix_world_from(None, force);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::CubeCamera;
+	use super::super::camera::HasCamera;
+	use super::super::super::core::object3d::HasObject3D;
+	use super::super::super::math::vector3::Vector3;
+
+	#[test]
+	fn faces_look_along_each_axis_from_the_rig_position() {
+		let mut cube_camera = CubeCamera::new(0.1, 100.0);
+		cube_camera.get_object3d_mut().get_position_mut().set(1.0, 2.0, 3.0);
+		cube_camera.update_matrix_world(false);
+
+		let axes = [
+			Vector3 { x: 1.0, y: 0.0, z: 0.0 },
+			Vector3 { x: - 1.0, y: 0.0, z: 0.0 },
+			Vector3 { x: 0.0, y: 1.0, z: 0.0 },
+			Vector3 { x: 0.0, y: - 1.0, z: 0.0 },
+			Vector3 { x: 0.0, y: 0.0, z: 1.0 },
+			Vector3 { x: 0.0, y: 0.0, z: - 1.0 },
+		];
+
+		for (camera, axis) in cube_camera.get_cameras().iter().zip(axes.iter()) {
+			assert_eq!(camera.fov, 90.0);
+
+			let mut direction = Vector3::new();
+			camera.get_camera().get_world_direction(&mut direction);
+			direction.sub(axis);
+			assert!(direction.length() < 1e-5);
+
+			let mut position = Vector3::new();
+			position.set_from_matrix_position(camera.get_object3d().get_matrix_world());
+			assert!(position.equals(&Vector3 { x: 1.0, y: 2.0, z: 3.0 }));
+		}
+	}
+}
@@ -1 +1,7 @@
-pub mod camera;
\ No newline at end of file
+#[macro_use]
+pub mod camera;
+pub mod perspective_camera;
+pub mod orthographic_camera;
+pub mod cube_camera;
+pub mod array_camera;
+pub mod stereo_camera;
\ No newline at end of file
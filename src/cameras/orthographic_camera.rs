@@ -0,0 +1,138 @@
+#[cfg(feature = "serde")]
+use serde_json::{Map, Value};
+use super::camera::{Camera, HasCamera, View};
+use super::super::core::object3d::{Object3D, HasObject3D};
+use super::super::math::matrix4::Matrix4;
+
+#[derive(Debug, Clone)]
+pub struct OrthographicCamera {
+	camera: Camera,
+	pub zoom: f32,
+	pub left: f32,
+	pub right: f32,
+	pub top: f32,
+	pub bottom: f32,
+	pub near: f32,
+	pub far: f32,
+	pub view: Option<View>,
+}
+
+impl HasCamera for OrthographicCamera {
+	fn get_camera(&self) -> &Camera {
+		&self.camera
+	}
+
+	fn get_camera_mut(&mut self) -> &mut Camera {
+		&mut self.camera
+	}
+}
+
+impl_camera_object3d!(OrthographicCamera);
+
+impl OrthographicCamera {
+	pub fn new(left: f32, right: f32, top: f32, bottom: f32, near: f32, far: f32) -> OrthographicCamera {
+		let mut camera = OrthographicCamera {
+			camera: Camera::new(),
+			zoom: 1.0,
+			left,
+			right,
+			top,
+			bottom,
+			near,
+			far,
+			view: None,
+		};
+		camera.update_projection_matrix();
+		camera
+	}
+
+	pub fn set_view_offset(&mut self, full_width: f32, full_height: f32, x: f32, y: f32, width: f32, height: f32) {
+		self.view = Some(View {
+			full_width,
+			full_height,
+			offset_x: x,
+			offset_y: y,
+			width,
+			height,
+		});
+		self.update_projection_matrix();
+	}
+
+	pub fn clear_view_offset(&mut self) {
+		self.view = None;
+		self.update_projection_matrix();
+	}
+
+	pub fn update_projection_matrix(&mut self) {
+		let dx = ( self.right - self.left ) / ( 2.0 * self.zoom );
+		let dy = ( self.top - self.bottom ) / ( 2.0 * self.zoom );
+		let cx = ( self.right + self.left ) / 2.0;
+		let cy = ( self.top + self.bottom ) / 2.0;
+
+		let mut left = cx - dx;
+		let mut right = cx + dx;
+		let mut top = cy + dy;
+		let mut bottom = cy - dy;
+
+		if let Some(view) = self.view {
+			let scale_w = ( self.right - self.left ) / view.full_width / self.zoom;
+			let scale_h = ( self.top - self.bottom ) / view.full_height / self.zoom;
+
+			left += scale_w * view.offset_x;
+			right = left + scale_w * view.width;
+			top -= scale_h * view.offset_y;
+			bottom = top - scale_h * view.height;
+		}
+
+		self.camera.projection_matrix.make_orthographic(left, right, bottom, top, self.near, self.far);
+	}
+
+	#[cfg(feature = "serde")]
+	fn write_projection_json(&self, object: &mut Map<String, Value>) {
+		object.insert("zoom".to_string(), Value::from(self.zoom));
+		object.insert("left".to_string(), Value::from(self.left));
+		object.insert("right".to_string(), Value::from(self.right));
+		object.insert("top".to_string(), Value::from(self.top));
+		object.insert("bottom".to_string(), Value::from(self.bottom));
+		object.insert("near".to_string(), Value::from(self.near));
+		object.insert("far".to_string(), Value::from(self.far));
+
+		if let Some(view) = self.view {
+			object.insert("view".to_string(), view.to_json());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::OrthographicCamera;
+	use super::super::super::math::vector3::Vector3;
+
+	#[test]
+	fn project_and_unproject_round_trip_with_zoom_and_view_offset() {
+		let mut camera = OrthographicCamera::new(-200.0, 200.0, 100.0, -100.0, 0.1, 100.0);
+		camera.zoom = 2.0;
+		camera.update_projection_matrix();
+
+		let mut v: Vector3 = Vector3 { x: 50.0, y: -25.0, z: -10.0 };
+		v.project(&camera).unwrap();
+		assert!((v.x - 0.5).abs() < 1e-5);
+		assert!((v.y + 0.5).abs() < 1e-5);
+
+		v.unproject(&camera).unwrap();
+		assert!((v.x - 50.0).abs() < 1e-3);
+		assert!((v.z + 10.0).abs() < 1e-3);
+
+		// the right half of the full view fills the whole tile, so the centre line is its left edge
+		camera.zoom = 1.0;
+		camera.set_view_offset(400.0, 200.0, 200.0, 0.0, 200.0, 200.0);
+		let mut v: Vector3 = Vector3 { x: 0.0, y: 0.0, z: -10.0 };
+		v.project(&camera).unwrap();
+		assert!((v.x + 1.0).abs() < 1e-5);
+
+		camera.clear_view_offset();
+		let mut v: Vector3 = Vector3 { x: 0.0, y: 0.0, z: -10.0 };
+		v.project(&camera).unwrap();
+		assert!(v.x.abs() < 1e-5);
+	}
+}
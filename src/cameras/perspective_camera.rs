@@ -0,0 +1,169 @@
+#[cfg(feature = "serde")]
+use serde_json::{Map, Value};
+use super::camera::{Camera, HasCamera, View};
+use super::super::core::object3d::{Object3D, HasObject3D};
+use super::super::math::matrix4::Matrix4;
+
+#[derive(Debug, Clone)]
+pub struct PerspectiveCamera {
+	camera: Camera,
+	pub fov: f32,
+	pub zoom: f32,
+	pub near: f32,
+	pub far: f32,
+	pub focus: f32,
+	pub aspect: f32,
+	pub view: Option<View>,
+	pub film_gauge: f32,
+	pub film_offset: f32,
+}
+
+impl HasCamera for PerspectiveCamera {
+	fn get_camera(&self) -> &Camera {
+		&self.camera
+	}
+
+	fn get_camera_mut(&mut self) -> &mut Camera {
+		&mut self.camera
+	}
+}
+
+impl_camera_object3d!(PerspectiveCamera);
+
+impl PerspectiveCamera {
+	pub fn new(fov: f32, aspect: f32, near: f32, far: f32) -> PerspectiveCamera {
+		let mut camera = PerspectiveCamera {
+			camera: Camera::new(),
+			fov,
+			zoom: 1.0,
+			near,
+			far,
+			focus: 10.0,
+			aspect,
+			view: None,
+			film_gauge: 35.0,
+			film_offset: 0.0,
+		};
+		camera.update_projection_matrix();
+		camera
+	}
+
+	// sets the fov by the focal length in respect to the current film gauge, 35mm by default
+	pub fn set_focal_length(&mut self, focal_length: f32) {
+		let v_extent_slope = 0.5 * self.get_film_height() / focal_length;
+		self.fov = ( 2.0 * v_extent_slope.atan() ).to_degrees();
+		self.update_projection_matrix();
+	}
+
+	pub fn get_focal_length(&self) -> f32 {
+		let v_extent_slope = ( 0.5 * self.fov.to_radians() ).tan();
+		0.5 * self.get_film_height() / v_extent_slope
+	}
+
+	pub fn get_effective_fov(&self) -> f32 {
+		( 2.0 * ( ( 0.5 * self.fov.to_radians() ).tan() / self.zoom ).atan() ).to_degrees()
+	}
+
+	pub fn get_film_width(&self) -> f32 {
+		// film not completely covered in portrait format (aspect < 1)
+		self.film_gauge * self.aspect.min(1.0)
+	}
+
+	pub fn get_film_height(&self) -> f32 {
+		// film not completely covered in landscape format (aspect > 1)
+		self.film_gauge / self.aspect.max(1.0)
+	}
+
+	// renders the sub-rectangle (x, y, width, height) of a full_width x full_height image,
+	// e.g. one monitor of a grid or one tile of a large screenshot
+	pub fn set_view_offset(&mut self, full_width: f32, full_height: f32, x: f32, y: f32, width: f32, height: f32) {
+		self.aspect = full_width / full_height;
+		self.view = Some(View {
+			full_width,
+			full_height,
+			offset_x: x,
+			offset_y: y,
+			width,
+			height,
+		});
+		self.update_projection_matrix();
+	}
+
+	pub fn clear_view_offset(&mut self) {
+		self.view = None;
+		self.update_projection_matrix();
+	}
+
+	pub fn update_projection_matrix(&mut self) {
+		let near = self.near;
+		let mut top = near * ( 0.5 * self.fov.to_radians() ).tan() / self.zoom;
+		let mut height = 2.0 * top;
+		let mut width = self.aspect * height;
+		let mut left = - 0.5 * width;
+
+		if let Some(view) = self.view {
+			left += view.offset_x * width / view.full_width;
+			top -= view.offset_y * height / view.full_height;
+			width *= view.width / view.full_width;
+			height *= view.height / view.full_height;
+		}
+
+		let skew = self.film_offset;
+		if skew != 0.0 {
+			left += near * skew / self.get_film_width();
+		}
+
+		self.camera.projection_matrix.make_frustum(left, left + width, top - height, top, near, self.far);
+	}
+
+	#[cfg(feature = "serde")]
+	fn write_projection_json(&self, object: &mut Map<String, Value>) {
+		object.insert("fov".to_string(), Value::from(self.fov));
+		object.insert("zoom".to_string(), Value::from(self.zoom));
+		object.insert("near".to_string(), Value::from(self.near));
+		object.insert("far".to_string(), Value::from(self.far));
+		object.insert("focus".to_string(), Value::from(self.focus));
+		object.insert("aspect".to_string(), Value::from(self.aspect));
+
+		if let Some(view) = self.view {
+			object.insert("view".to_string(), view.to_json());
+		}
+
+		object.insert("filmGauge".to_string(), Value::from(self.film_gauge));
+		object.insert("filmOffset".to_string(), Value::from(self.film_offset));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::PerspectiveCamera;
+	use super::super::camera::HasCamera;
+	use super::super::super::math::matrix4::Matrix4;
+	use super::super::super::math::vector3::Vector3;
+
+	#[test]
+	fn projection_matches_make_perspective_and_tiles_with_view_offset() {
+		let mut camera = PerspectiveCamera::new(60.0, 2.0, 1.0, 100.0);
+
+		let mut expected = Matrix4::new();
+		expected.make_perspective(60.0, 2.0, 1.0, 100.0);
+		for (a, b) in camera.get_camera().get_projection_matrix().elements.iter().zip(expected.elements.iter()) {
+			assert!((a - b).abs() < 1e-6);
+		}
+
+		// the centre of the top-left quarter of the full view is the centre of the top-left tile
+		let mut point = Vector3 { x: -0.5, y: 0.5, z: 0.5 };
+		point.apply_projection(&camera.get_camera().get_projection_matrix().try_inverse().unwrap());
+		camera.set_view_offset(200.0, 100.0, 0.0, 0.0, 100.0, 50.0);
+
+		let mut projected = point;
+		projected.apply_projection(camera.get_camera().get_projection_matrix());
+		assert!(projected.x.abs() < 1e-5);
+		assert!(projected.y.abs() < 1e-5);
+
+		camera.clear_view_offset();
+		let focal_length = camera.get_focal_length();
+		camera.set_focal_length(focal_length);
+		assert!((camera.fov - 60.0).abs() < 1e-3);
+	}
+}
@@ -0,0 +1,141 @@
+use super::camera::HasCamera;
+use super::perspective_camera::PerspectiveCamera;
+use super::super::core::object3d::HasObject3D;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StereoCache {
+	focus: f32,
+	fov: f32,
+	aspect: f32,
+	near: f32,
+	far: f32,
+	zoom: f32,
+	eye_sep: f32,
+}
+
+// splits a perspective camera into a left and right eye with off-axis frustums that
+// converge on the camera's focus distance. the left eye sees layer 1, the right layer 2
+#[derive(Debug, Clone)]
+pub struct StereoCamera {
+	pub aspect: f32,
+	pub eye_sep: f32,
+	camera_l: PerspectiveCamera,
+	camera_r: PerspectiveCamera,
+	cache: Option<StereoCache>,
+}
+
+impl StereoCamera {
+	pub fn new() -> StereoCamera {
+		let mut camera_l = PerspectiveCamera::new(50.0, 1.0, 0.1, 2000.0);
+		camera_l.get_object3d_mut().get_layers_mut().enable(1);
+
+		let mut camera_r = PerspectiveCamera::new(50.0, 1.0, 0.1, 2000.0);
+		camera_r.get_object3d_mut().get_layers_mut().enable(2);
+
+		StereoCamera {
+			aspect: 1.0,
+			eye_sep: 0.064,
+			camera_l,
+			camera_r,
+			cache: None,
+		}
+	}
+
+	pub fn get_camera_l(&self) -> &PerspectiveCamera {
+		&self.camera_l
+	}
+
+	pub fn get_camera_r(&self) -> &PerspectiveCamera {
+		&self.camera_r
+	}
+
+	// the eye projections are only rebuilt when the camera or stereo parameters changed,
+	// the eye positions follow the camera's world matrix on every call
+	pub fn update(&mut self, camera: &PerspectiveCamera) {
+		let cache = StereoCache {
+			focus: camera.focus,
+			fov: camera.fov,
+			aspect: camera.aspect * self.aspect,
+			near: camera.near,
+			far: camera.far,
+			zoom: camera.zoom,
+			eye_sep: self.eye_sep,
+		};
+
+		let eye_sep_half = cache.eye_sep / 2.0;
+
+		if self.cache != Some(cache) {
+			// off-axis stereoscopic projection,
+			// see http://paulbourke.net/stereographics/stereorender/
+			let eye_sep_on_projection = eye_sep_half * cache.near / cache.focus;
+			let ymax = ( cache.near * ( cache.fov.to_radians() * 0.5 ).tan() ) / cache.zoom;
+
+			// for left eye
+			let xmin = - ymax * cache.aspect + eye_sep_on_projection;
+			let xmax = ymax * cache.aspect + eye_sep_on_projection;
+			self.camera_l.get_camera_mut().projection_matrix.make_frustum(xmin, xmax, - ymax, ymax, cache.near, cache.far);
+
+			// for right eye
+			let xmin = - ymax * cache.aspect - eye_sep_on_projection;
+			let xmax = ymax * cache.aspect - eye_sep_on_projection;
+			self.camera_r.get_camera_mut().projection_matrix.make_frustum(xmin, xmax, - ymax, ymax, cache.near, cache.far);
+
+			self.cache = Some(cache);
+		}
+
+		let matrix_world = camera.get_object3d().get_matrix_world();
+		self.camera_l.get_object3d_mut().get_position_mut().set(- eye_sep_half, 0.0, 0.0);
+		self.camera_l.update_world_matrix_from(Some(matrix_world));
+		self.camera_r.get_object3d_mut().get_position_mut().set(eye_sep_half, 0.0, 0.0);
+		self.camera_r.update_world_matrix_from(Some(matrix_world));
+	}
+}
+
+impl Default for StereoCamera {
+	fn default() -> StereoCamera {
+		StereoCamera::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::StereoCamera;
+	use super::super::camera::HasCamera;
+	use super::super::perspective_camera::PerspectiveCamera;
+	use super::super::super::core::object3d::HasObject3D;
+	use super::super::super::math::vector3::Vector3;
+
+	#[test]
+	fn eyes_converge_on_the_focus_distance() {
+		let mut camera = PerspectiveCamera::new(60.0, 1.5, 0.1, 100.0);
+		camera.focus = 4.0;
+		camera.get_object3d_mut().get_position_mut().set(0.0, 1.0, 0.0);
+		camera.get_camera_mut().update_matrix_world(false);
+
+		let mut stereo = StereoCamera::new();
+		stereo.eye_sep = 0.1;
+		stereo.update(&camera);
+
+		let mut eye_l = Vector3::new();
+		eye_l.set_from_matrix_position(stereo.get_camera_l().get_object3d().get_matrix_world());
+		assert!(eye_l.equals(&Vector3 { x: - 0.05, y: 1.0, z: 0.0 }));
+
+		// a point straight ahead at the focus distance has zero parallax, nearer points don't
+		let mut at_focus_l: Vector3 = Vector3 { x: 0.0, y: 1.0, z: - 4.0 };
+		let mut at_focus_r = at_focus_l;
+		at_focus_l.project(stereo.get_camera_l()).unwrap();
+		at_focus_r.project(stereo.get_camera_r()).unwrap();
+		assert!(at_focus_l.x.abs() < 1e-5 && at_focus_r.x.abs() < 1e-5);
+
+		let mut near_l: Vector3 = Vector3 { x: 0.0, y: 1.0, z: - 1.0 };
+		let mut near_r = near_l;
+		near_l.project(stereo.get_camera_l()).unwrap();
+		near_r.project(stereo.get_camera_r()).unwrap();
+		assert!(near_l.x > 0.0 && near_r.x < 0.0);
+
+		let before = stereo.get_camera_l().get_camera().projection_matrix;
+		stereo.eye_sep = 0.2;
+		stereo.update(&camera);
+		assert!(before.elements[ 8 ] < stereo.get_camera_l().get_camera().projection_matrix.elements[ 8 ]);
+	}
+}
@@ -0,0 +1,164 @@
+use super::super::math::matrix3::Matrix3;
+use super::super::math::matrix4::Matrix4;
+use super::super::math::vector3::Vector3;
+use super::super::errors::BufferAttributeError;
+
+// a flat array of per-vertex items, item_size components each. normalized marks
+// integer data that shaders should read as 0 to 1 (or -1 to 1)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferAttribute<T = f32> {
+	array: Vec<T>,
+	item_size: usize,
+	normalized: bool,
+}
+
+impl<T: Copy> BufferAttribute<T> {
+	// the array has to hold a whole number of items
+	pub fn new(array: Vec<T>, item_size: usize, normalized: bool) -> Result<BufferAttribute<T>, BufferAttributeError> {
+		check_length(array.len(), item_size)?;
+
+		Ok(BufferAttribute {
+			array,
+			item_size,
+			normalized,
+		})
+	}
+
+	pub fn get_array(&self) -> &[T] {
+		&self.array
+	}
+
+	pub fn get_array_mut(&mut self) -> &mut [T] {
+		&mut self.array
+	}
+
+	pub fn get_item_size(&self) -> usize {
+		self.item_size
+	}
+
+	pub fn get_count(&self) -> usize {
+		self.array.len() / self.item_size
+	}
+
+	pub fn is_normalized(&self) -> bool {
+		self.normalized
+	}
+
+	pub fn set_normalized(&mut self, normalized: bool) {
+		self.normalized = normalized;
+	}
+
+	pub fn get_component(&self, index: usize, component: usize) -> T {
+		debug_assert!(component < self.item_size, "component {} of an item of {}", component, self.item_size);
+		self.array[index * self.item_size + component]
+	}
+
+	pub fn set_component(&mut self, index: usize, component: usize, value: T) {
+		debug_assert!(component < self.item_size, "component {} of an item of {}", component, self.item_size);
+		self.array[index * self.item_size + component] = value;
+	}
+
+	pub fn copy_array(&mut self, array: &[T]) -> Result<(), BufferAttributeError> {
+		check_length(array.len(), self.item_size)?;
+
+		self.array.clear();
+		self.array.extend_from_slice(array);
+		Ok(())
+	}
+}
+
+impl BufferAttribute {
+	pub fn get_x(&self, index: usize) -> f32 {
+		self.get_component(index, 0)
+	}
+
+	pub fn get_y(&self, index: usize) -> f32 {
+		self.get_component(index, 1)
+	}
+
+	pub fn get_z(&self, index: usize) -> f32 {
+		self.get_component(index, 2)
+	}
+
+	pub fn get_w(&self, index: usize) -> f32 {
+		self.get_component(index, 3)
+	}
+
+	pub fn set_xyz(&mut self, index: usize, x: f32, y: f32, z: f32) {
+		debug_assert!(self.item_size >= 3, "xyz of an item of {}", self.item_size);
+		let offset = index * self.item_size;
+		self.array[offset] = x;
+		self.array[offset + 1] = y;
+		self.array[offset + 2] = z;
+	}
+
+	// the following work on items of 3. get_vector3 returns None and the transforms
+	// leave attributes of any other item size untouched
+
+	pub fn get_vector3(&self, index: usize) -> Option<Vector3> {
+		if self.item_size != 3 {
+			return None;
+		}
+
+		let mut v = Vector3::new();
+		v.copy_from_array(&self.array, Some(index * 3));
+		Some(v)
+	}
+
+	pub fn apply_matrix4(&mut self, m: &Matrix4) {
+		if self.item_size != 3 {
+			return;
+		}
+
+		m.apply_to_vector3_array(&mut self.array, None, None);
+	}
+
+	pub fn apply_normal_matrix(&mut self, m: &Matrix3) {
+		if self.item_size != 3 {
+			return;
+		}
+
+		m.apply_to_vector3_array(&mut self.array, None, None);
+		self.normalize_items();
+	}
+
+	pub fn transform_direction(&mut self, m: &Matrix4) {
+		if self.item_size != 3 {
+			return;
+		}
+
+		let mut v1 = Vector3::new();
+		for i in 0..self.get_count() {
+			v1.copy_from_array(&self.array, Some(i * self.item_size));
+			v1.transform_direction(m);
+			v1.copy_to_array(&mut self.array, Some(i * self.item_size));
+		}
+	}
+
+	fn normalize_items(&mut self) {
+		let mut v1 = Vector3::new();
+		for i in 0..self.get_count() {
+			v1.copy_from_array(&self.array, Some(i * self.item_size));
+			v1.normalize();
+			v1.copy_to_array(&mut self.array, Some(i * self.item_size));
+		}
+	}
+}
+
+// % rather than is_multiple_of, which needs rust 1.87
+#[allow(unknown_lints, clippy::manual_is_multiple_of)]
+fn check_length(len: usize, item_size: usize) -> Result<(), BufferAttributeError> {
+	if item_size == 0 {
+		return Err(BufferAttributeError {
+			reason: "item size is 0".to_string(),
+		});
+	}
+
+	if len % item_size != 0 {
+		return Err(BufferAttributeError {
+			reason: format!("{} values don't split into items of {}", len, item_size),
+		});
+	}
+
+	Ok(())
+}
@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+use super::buffer_attribute::BufferAttribute;
+use super::super::math::matrix3::Matrix3;
+use super::super::math::matrix4::Matrix4;
+use super::super::math::vector3::Vector3;
+use super::super::math::box3::Box3;
+use super::super::math::sphere::Sphere;
+use super::super::errors::{SingularMatrixError, BufferAttributeError};
+
+// attributes transformed and measured as 3D vectors, so they must have an item size of 3
+const VECTOR3_ATTRIBUTES: [&str; 3] = [ "position", "normal", "tangent" ];
+
+// a range of the index (or of the vertices, without one) drawn with one material
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Group {
+	pub start: usize,
+	pub count: usize,
+	pub material_index: usize,
+}
+
+// count is None to draw everything from start on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrawRange {
+	pub start: usize,
+	pub count: Option<usize>,
+}
+
+// vertex data as named attributes, "position", "normal", "uv", "color" or custom ones.
+// positions, normals and tangents always have an item size of 3, set_attribute refuses others
+#[derive(Debug, Clone)]
+pub struct BufferGeometry {
+	name: String,
+	index: Option<BufferAttribute<u32>>,
+	attributes: HashMap<String, BufferAttribute>,
+	groups: Vec<Group>,
+	draw_range: DrawRange,
+	bounding_box: Option<Box3>,
+	bounding_sphere: Option<Sphere>,
+}
+
+impl BufferGeometry {
+	pub fn new() -> BufferGeometry {
+		BufferGeometry {
+			name: String::new(),
+			index: None,
+			attributes: HashMap::new(),
+			groups: vec![],
+			draw_range: DrawRange {
+				start: 0,
+				count: None,
+			},
+			bounding_box: None,
+			bounding_sphere: None,
+		}
+	}
+
+	pub fn get_name(&self) -> &str {
+		&self.name
+	}
+
+	pub fn set_name(&mut self, name: &str) {
+		self.name = name.to_string();
+	}
+
+	pub fn get_index(&self) -> Option<&BufferAttribute<u32>> {
+		self.index.as_ref()
+	}
+
+	pub fn set_index(&mut self, index: Option<BufferAttribute<u32>>) {
+		self.index = index;
+	}
+
+	pub fn get_attribute(&self, name: &str) -> Option<&BufferAttribute> {
+		self.attributes.get(name)
+	}
+
+	pub fn get_attribute_mut(&mut self, name: &str) -> Option<&mut BufferAttribute> {
+		self.attributes.get_mut(name)
+	}
+
+	pub fn set_attribute(&mut self, name: &str, attribute: BufferAttribute) -> Result<(), BufferAttributeError> {
+		if VECTOR3_ATTRIBUTES.contains(&name) && attribute.get_item_size() != 3 {
+			return Err(BufferAttributeError {
+				reason: format!("{} needs an item size of 3, not {}", name, attribute.get_item_size()),
+			});
+		}
+
+		self.attributes.insert(name.to_string(), attribute);
+		Ok(())
+	}
+
+	pub fn delete_attribute(&mut self, name: &str) -> Option<BufferAttribute> {
+		self.attributes.remove(name)
+	}
+
+	pub fn has_attribute(&self, name: &str) -> bool {
+		self.attributes.contains_key(name)
+	}
+
+	pub fn get_attributes(&self) -> &HashMap<String, BufferAttribute> {
+		&self.attributes
+	}
+
+	pub fn get_groups(&self) -> &[Group] {
+		&self.groups
+	}
+
+	pub fn add_group(&mut self, start: usize, count: usize, material_index: usize) {
+		self.groups.push(Group {
+			start,
+			count,
+			material_index,
+		});
+	}
+
+	pub fn clear_groups(&mut self) {
+		self.groups.clear();
+	}
+
+	pub fn get_draw_range(&self) -> &DrawRange {
+		&self.draw_range
+	}
+
+	pub fn set_draw_range(&mut self, start: usize, count: Option<usize>) {
+		self.draw_range.start = start;
+		self.draw_range.count = count;
+	}
+
+	pub fn get_bounding_box(&self) -> Option<&Box3> {
+		self.bounding_box.as_ref()
+	}
+
+	pub fn get_bounding_sphere(&self) -> Option<&Sphere> {
+		self.bounding_sphere.as_ref()
+	}
+
+	// normals go through the normal matrix, which fails for a singular matrix.
+	// bounds that were already computed are kept up to date
+	pub fn apply_matrix4(&mut self, matrix: &Matrix4) -> Result<(), SingularMatrixError> {
+		let normal_matrix = if self.attributes.contains_key("normal") {
+			let mut m = Matrix3::new();
+			m.get_normal_matrix(matrix)?;
+			Some(m)
+		} else {
+			None
+		};
+
+		if let Some(position) = self.attributes.get_mut("position") {
+			position.apply_matrix4(matrix);
+		}
+
+		if let (Some(normal), Some(normal_matrix)) = (self.attributes.get_mut("normal"), normal_matrix) {
+			normal.apply_normal_matrix(&normal_matrix);
+		}
+
+		if let Some(tangent) = self.attributes.get_mut("tangent") {
+			tangent.transform_direction(matrix);
+		}
+
+		if self.bounding_box.is_some() {
+			self.compute_bounding_box();
+		}
+
+		if self.bounding_sphere.is_some() {
+			self.compute_bounding_sphere();
+		}
+
+		Ok(())
+	}
+
+	// a non-finite angle gives a matrix without an inverse, an error when the geometry has normals
+	pub fn rotate_x(&mut self, angle: f32) -> Result<(), SingularMatrixError> {
+		let mut m1 = Matrix4::new();
+		m1.make_rotation_x(angle);
+		self.apply_matrix4(&m1)
+	}
+
+	pub fn rotate_y(&mut self, angle: f32) -> Result<(), SingularMatrixError> {
+		let mut m1 = Matrix4::new();
+		m1.make_rotation_y(angle);
+		self.apply_matrix4(&m1)
+	}
+
+	pub fn rotate_z(&mut self, angle: f32) -> Result<(), SingularMatrixError> {
+		let mut m1 = Matrix4::new();
+		m1.make_rotation_z(angle);
+		self.apply_matrix4(&m1)
+	}
+
+	pub fn translate(&mut self, x: f32, y: f32, z: f32) {
+		let mut m1 = Matrix4::new();
+		m1.make_translation(x, y, z);
+		self.apply_matrix4(&m1).expect("translations are invertible");
+	}
+
+	// a zero factor flattens the normals, so it is an error when the geometry has any
+	pub fn scale(&mut self, x: f32, y: f32, z: f32) -> Result<(), SingularMatrixError> {
+		let mut m1 = Matrix4::new();
+		m1.make_scale(x, y, z);
+		self.apply_matrix4(&m1)
+	}
+
+	// moves the geometry so its bounding box is centered on the origin
+	pub fn center(&mut self) {
+		self.compute_bounding_box();
+
+		let mut offset = Vector3::new();
+		if let Some(ref bounding_box) = self.bounding_box {
+			bounding_box.get_center(&mut offset);
+		}
+
+		self.translate(- offset.x, - offset.y, - offset.z);
+	}
+
+	// an empty box without a position attribute
+	pub fn compute_bounding_box(&mut self) {
+		let mut bounding_box = Box3::new();
+
+		if let Some(position) = self.attributes.get("position") {
+			bounding_box.set_from_array(position.get_array());
+		}
+
+		self.bounding_box = Some(bounding_box);
+	}
+
+	pub fn compute_bounding_sphere(&mut self) {
+		let mut bounding_sphere = Sphere::new();
+
+		if let Some(position) = self.attributes.get("position") {
+			// first, find the center of the bounding sphere
+			let mut bounding_box = Box3::new();
+			bounding_box.set_from_array(position.get_array());
+
+			let mut center = Vector3::new();
+			bounding_box.get_center(&mut center);
+
+			// second, try to find a bounding sphere with a radius smaller than the
+			// bounding sphere of the bounding box
+			let mut max_radius_sq: f32 = 0.0;
+			for v1 in (0..position.get_count()).filter_map(|i| position.get_vector3(i)) {
+				max_radius_sq = max_radius_sq.max(center.distance_to_squared(&v1));
+			}
+
+			bounding_sphere.set(&center, max_radius_sq.sqrt());
+		}
+
+		self.bounding_sphere = Some(bounding_sphere);
+	}
+}
+
+impl Default for BufferGeometry {
+	fn default() -> BufferGeometry {
+		BufferGeometry::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::BufferGeometry;
+	use super::super::buffer_attribute::BufferAttribute;
+	use super::super::super::math::matrix4::Matrix4;
+	use super::super::super::math::vector3::Vector3;
+
+	fn quad() -> BufferGeometry {
+		let mut geometry = BufferGeometry::new();
+		geometry.set_attribute("position", BufferAttribute::new(vec![
+			0.0, 0.0, 0.0,
+			2.0, 0.0, 0.0,
+			2.0, 2.0, 0.0,
+			0.0, 2.0, 0.0,
+		], 3, false).unwrap()).unwrap();
+		geometry.set_attribute("normal", BufferAttribute::new(vec![
+			0.0, 0.0, 1.0,
+			0.0, 0.0, 1.0,
+			0.0, 0.0, 1.0,
+			0.0, 0.0, 1.0,
+		], 3, false).unwrap()).unwrap();
+		geometry.set_index(Some(BufferAttribute::new(vec![0, 1, 2, 0, 2, 3], 1, false).unwrap()));
+		geometry.add_group(0, 3, 0);
+		geometry.add_group(3, 3, 1);
+		geometry
+	}
+
+	#[test]
+	fn bounds_follow_transforms() {
+		let mut geometry = quad();
+		geometry.compute_bounding_box();
+		geometry.compute_bounding_sphere();
+		assert!(geometry.get_bounding_box().unwrap().max.equals(&Vector3 { x: 2.0, y: 2.0, z: 0.0 }));
+		assert!((geometry.get_bounding_sphere().unwrap().radius - 2.0f32.sqrt()).abs() < 1e-6);
+
+		geometry.center();
+		geometry.translate(0.0, 0.0, 5.0);
+		let bounding_box = *geometry.get_bounding_box().unwrap();
+		assert!(bounding_box.min.equals(&Vector3 { x: - 1.0, y: - 1.0, z: 5.0 }));
+		assert!(geometry.get_bounding_sphere().unwrap().center.equals(&Vector3 { x: 0.0, y: 0.0, z: 5.0 }));
+		assert_eq!(geometry.get_index().unwrap().get_count(), 6);
+		assert_eq!(geometry.get_groups()[ 1 ].material_index, 1);
+
+		assert_eq!(geometry.get_draw_range().count, None);
+		geometry.set_draw_range(3, Some(3));
+		assert_eq!((geometry.get_draw_range().start, geometry.get_draw_range().count), (3, Some(3)));
+	}
+
+	#[test]
+	fn attributes_are_validated() {
+		assert!(BufferAttribute::new(vec![0.0; 3], 0, false).is_err());
+		assert!(BufferAttribute::new(vec![0.0; 4], 3, false).is_err());
+
+		let mut colors = BufferAttribute::new(vec![255u8, 0, 0, 255], 4, true).unwrap();
+		assert!(colors.is_normalized());
+		assert_eq!(colors.get_count(), 1);
+		assert!(colors.copy_array(&[0, 255]).is_err());
+		colors.set_normalized(false);
+		assert!(!colors.is_normalized());
+
+		// 2D positions would be read as 3D vectors by the transforms and bounds
+		let mut geometry = quad();
+		let flat = BufferAttribute::new(vec![0.0, 0.0, 1.0, 1.0], 2, false).unwrap();
+		assert!(geometry.set_attribute("position", flat.clone()).is_err());
+		assert!(geometry.set_attribute("uv", flat).is_ok());
+		geometry.rotate_z(1.0).unwrap();
+		assert_eq!(geometry.get_attribute("uv").unwrap().get_array(), &[0.0, 0.0, 1.0, 1.0]);
+
+		assert!(geometry.rotate_x(f32::NAN).is_err());
+	}
+
+	#[test]
+	fn normals_use_the_normal_matrix() {
+		let mut geometry = quad();
+
+		// a non-uniform scale shears positions but must keep normals unit length
+		let mut m = Matrix4::new();
+		m.make_rotation_x(::std::f32::consts::FRAC_PI_2);
+		m.scale(&Vector3 { x: 1.0, y: 3.0, z: 1.0 });
+		geometry.apply_matrix4(&m).unwrap();
+
+		let normal = geometry.get_attribute("normal").unwrap().get_vector3(2).unwrap();
+		assert!((normal.length() - 1.0).abs() < 1e-6);
+		assert!((normal.y + 1.0).abs() < 1e-6);
+
+		let position = geometry.get_attribute("position").unwrap();
+		assert!((position.get_z(2) - 6.0).abs() < 1e-5);
+
+		assert!(geometry.scale(0.0, 1.0, 1.0).is_err());
+		geometry.delete_attribute("normal");
+		assert!(geometry.scale(0.0, 1.0, 1.0).is_ok());
+
+		let uv = BufferAttribute::new(vec![0.0, 0.0, 1.0, 0.0, 1.0, 1.0], 2, false).unwrap();
+		assert!(uv.get_vector3(0).is_none());
+		assert_eq!(uv.get_y(2), 1.0);
+	}
+}
@@ -0,0 +1,281 @@
+use std::time::Instant;
+
+// a source of time in seconds, sampled whenever the clock needs to know "now"
+pub trait TimeSource {
+	fn now(&mut self) -> f64;
+}
+
+// reads the wall clock, measured from when the source was created
+#[derive(Debug, Clone, Copy)]
+pub struct WallClock {
+	origin: Instant,
+}
+
+impl WallClock {
+	pub fn new() -> WallClock {
+		WallClock {
+			origin: Instant::now(),
+		}
+	}
+}
+
+impl Default for WallClock {
+	fn default() -> WallClock {
+		WallClock::new()
+	}
+}
+
+impl TimeSource for WallClock {
+	fn now(&mut self) -> f64 {
+		let elapsed = self.origin.elapsed();
+		elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 * 1e-9
+	}
+}
+
+// advances by a fixed step every time it is sampled, useful for offline renders
+#[derive(Debug, Clone, Copy)]
+pub struct FixedStep {
+	time: f64,
+	step: f64,
+}
+
+impl FixedStep {
+	pub fn new(step: f64) -> FixedStep {
+		FixedStep {
+			time: 0.0,
+			step,
+		}
+	}
+
+	pub fn get_step(&self) -> f64 {
+		self.step
+	}
+
+	pub fn set_step(&mut self, step: f64) {
+		self.step = step;
+	}
+}
+
+impl TimeSource for FixedStep {
+	fn now(&mut self) -> f64 {
+		let now = self.time;
+		self.time += self.step;
+		now
+	}
+}
+
+// only moves when told to, so time can be set, advanced or held still
+#[derive(Debug, Clone, Copy)]
+pub struct ManualTime {
+	time: f64,
+}
+
+impl ManualTime {
+	pub fn new() -> ManualTime {
+		ManualTime {
+			time: 0.0,
+		}
+	}
+
+	pub fn set_time(&mut self, time: f64) {
+		self.time = time;
+	}
+
+	pub fn advance(&mut self, seconds: f64) {
+		self.time += seconds;
+	}
+}
+
+impl Default for ManualTime {
+	fn default() -> ManualTime {
+		ManualTime::new()
+	}
+}
+
+impl TimeSource for ManualTime {
+	fn now(&mut self) -> f64 {
+		self.time
+	}
+}
+
+// wraps another source and scales how fast its time passes, a scale of 0 pauses it
+#[derive(Debug, Clone, Copy)]
+pub struct ScaledTime<S> {
+	source: S,
+	scale: f64,
+	last_source_time: Option<f64>,
+	time: f64,
+}
+
+impl<S: TimeSource> ScaledTime<S> {
+	pub fn new(source: S, scale: f64) -> ScaledTime<S> {
+		ScaledTime {
+			source,
+			scale,
+			last_source_time: None,
+			time: 0.0,
+		}
+	}
+
+	pub fn get_scale(&self) -> f64 {
+		self.scale
+	}
+
+	pub fn set_scale(&mut self, scale: f64) {
+		self.scale = scale;
+	}
+
+	pub fn pause(&mut self) {
+		self.set_scale(0.0);
+	}
+
+	pub fn get_source(&self) -> &S {
+		&self.source
+	}
+
+	pub fn get_source_mut(&mut self) -> &mut S {
+		&mut self.source
+	}
+}
+
+impl<S: TimeSource> TimeSource for ScaledTime<S> {
+	fn now(&mut self) -> f64 {
+		let source_time = self.source.now();
+
+		// changing the scale only affects time passing from now on
+		if let Some(last) = self.last_source_time {
+			self.time += ( source_time - last ) * self.scale;
+		}
+
+		self.last_source_time = Some(source_time);
+		self.time
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Clock<S = WallClock> {
+	time_source: S,
+	auto_start: bool,
+	start_time: f64,
+	old_time: f64,
+	elapsed_time: f64,
+	running: bool,
+}
+
+impl Clock<WallClock> {
+	pub fn new(auto_start: bool) -> Clock<WallClock> {
+		Clock::with_time_source(WallClock::new(), auto_start)
+	}
+}
+
+impl<S: TimeSource> Clock<S> {
+	pub fn with_time_source(time_source: S, auto_start: bool) -> Clock<S> {
+		Clock {
+			time_source,
+			auto_start,
+			start_time: 0.0,
+			old_time: 0.0,
+			elapsed_time: 0.0,
+			running: false,
+		}
+	}
+
+	pub fn get_time_source(&self) -> &S {
+		&self.time_source
+	}
+
+	pub fn get_time_source_mut(&mut self) -> &mut S {
+		&mut self.time_source
+	}
+
+	pub fn get_auto_start(&self) -> bool {
+		self.auto_start
+	}
+
+	pub fn set_auto_start(&mut self, auto_start: bool) {
+		self.auto_start = auto_start;
+	}
+
+	pub fn get_start_time(&self) -> f64 {
+		self.start_time
+	}
+
+	pub fn is_running(&self) -> bool {
+		self.running
+	}
+
+	pub fn start(&mut self) {
+		self.start_time = self.time_source.now();
+		self.old_time = self.start_time;
+		self.elapsed_time = 0.0;
+		self.running = true;
+	}
+
+	pub fn stop(&mut self) {
+		self.get_elapsed_time();
+		self.running = false;
+		self.auto_start = false;
+	}
+
+	pub fn get_elapsed_time(&mut self) -> f64 {
+		self.get_delta();
+		self.elapsed_time
+	}
+
+	pub fn get_delta(&mut self) -> f64 {
+		let mut diff = 0.0;
+
+		if self.auto_start && ! self.running {
+			self.start();
+			return 0.0;
+		}
+
+		if self.running {
+			let new_time = self.time_source.now();
+			diff = new_time - self.old_time;
+			self.old_time = new_time;
+			self.elapsed_time += diff;
+		}
+
+		diff
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Clock, FixedStep, ManualTime, ScaledTime};
+
+	#[test]
+	fn clock_follows_injected_time() {
+		let mut clock = Clock::with_time_source(ManualTime::new(), true);
+		assert_eq!(clock.get_delta(), 0.0);
+		assert!(clock.is_running());
+
+		clock.get_time_source_mut().advance(0.5);
+		assert_eq!(clock.get_delta(), 0.5);
+		clock.get_time_source_mut().advance(0.25);
+		assert_eq!(clock.get_elapsed_time(), 0.75);
+
+		clock.stop();
+		clock.get_time_source_mut().advance(10.0);
+		assert_eq!(clock.get_delta(), 0.0);
+		assert_eq!(clock.get_elapsed_time(), 0.75);
+	}
+
+	#[test]
+	fn fixed_step_and_scaled_time() {
+		let mut clock = Clock::with_time_source(FixedStep::new(1.0 / 60.0), false);
+		assert_eq!(clock.get_delta(), 0.0);
+		clock.start();
+		assert_eq!(clock.get_delta(), 1.0 / 60.0);
+
+		let mut clock = Clock::with_time_source(ScaledTime::new(ManualTime::new(), 2.0), true);
+		clock.start();
+		clock.get_time_source_mut().get_source_mut().advance(1.0);
+		assert_eq!(clock.get_delta(), 2.0);
+
+		clock.get_time_source_mut().pause();
+		clock.get_time_source_mut().get_source_mut().advance(1.0);
+		assert_eq!(clock.get_delta(), 0.0);
+	}
+}
@@ -1,4 +1,6 @@
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Layers {
 	pub mask: u64,
 }
@@ -29,4 +31,4 @@ impl Layers {
 	pub fn test(&self, layers: &Layers) -> bool {
 		(self.mask & layers.mask) != 0u64
 	}
-}
\ No newline at end of file
+}
@@ -1,3 +1,6 @@
 pub mod clock;
 pub mod layers;
-pub mod object3d;
\ No newline at end of file
+pub mod object3d;
+pub mod raycaster;
+pub mod buffer_attribute;
+pub mod buffer_geometry;
\ No newline at end of file
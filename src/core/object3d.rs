@@ -1,7 +1,9 @@
 extern crate uuid;
-use std::rc::{Rc, Weak};
-use std::cell::{Ref, RefMut, RefCell};
+#[cfg(feature = "serde")]
+use serde_json::{Map, Value};
+use std::any::Any;
 use std::cmp::{Eq, PartialEq};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use self::uuid::Uuid;
 use super::super::math::vector3::Vector3;
 use super::super::math::quaternion::Quaternion;
@@ -9,6 +11,9 @@ use super::super::math::matrix4::Matrix4;
 use super::super::math::matrix3::Matrix3;
 use super::super::math::euler::Euler;
 use super::layers::Layers;
+use super::raycaster::{Raycaster, Intersection};
+use super::super::scenes::scene::NodeId;
+use super::super::errors::SingularMatrixError;
 
 pub static mut DEFAULT_UP: Vector3 = Vector3 {
 	x: 0.0,
@@ -17,16 +22,44 @@ pub static mut DEFAULT_UP: Vector3 = Vector3 {
 };
 pub static mut DEFAULT_MATRIX_AUTO_UPDATE: bool = true;
 
-pub trait HasObject3D {
+static OBJECT3D_ID: AtomicUsize = AtomicUsize::new(0);
+
+pub trait HasObject3D: Any {
 	fn get_object3d(&self) -> &Object3D;
 	fn get_object3d_mut(&mut self) -> &mut Object3D;
+
+	// the three.js class name, written as "type" in the JSON object format
+	fn get_type(&self) -> &'static str {
+		"Object3D"
+	}
+
+	// adds the state of the concrete type to the JSON object Object3D::to_json_object wrote,
+	// e.g. a camera's projection
+	#[cfg(feature = "serde")]
+	fn write_json_fields(&self, _object: &mut Map<String, Value>) {
+	}
+
+	// called by the scene while walking the hierarchy. types that derive state from the
+	// world matrix, like cameras, override these to refresh it after the update
+	fn update_matrix_world_from(&mut self, parent_matrix_world: Option<&Matrix4>, force: bool) -> bool {
+		self.get_object3d_mut().update_matrix_world_from(parent_matrix_world, force)
+	}
+
+	fn update_world_matrix_from(&mut self, parent_matrix_world: Option<&Matrix4>) {
+		self.get_object3d_mut().update_world_matrix_from(parent_matrix_world);
+	}
+
+	// pushes the hits of the raycaster's world space ray on this node, tagged with its id.
+	// plain objects have nothing to hit
+	fn raycast(&self, _raycaster: &Raycaster, _id: NodeId, _intersects: &mut Vec<Intersection>) {
+	}
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct Object3D {
+	id: usize,
 	uuid: Uuid,
-	name: &'static str,
-	children: Vec<Rc<RefCell<HasObject3D>>>,
+	name: String,
 	up: Vector3,
 	position: Vector3,
 	quaternion: Quaternion,
@@ -43,7 +76,6 @@ pub struct Object3D {
 	receive_shadow: bool,
 	frustum_culled: bool,
 	render_order: u32,
-	parent: Option<Weak<RefCell<HasObject3D>>>,
 }
 
 impl HasObject3D for Object3D {
@@ -65,9 +97,9 @@ impl PartialEq for Object3D {
 impl Object3D {
 	pub fn new() -> Object3D {
 		Object3D {
+			id: OBJECT3D_ID.fetch_add(1, Ordering::Relaxed),
 			uuid: Uuid::new_v4(),
-			name: "",
-			children: vec![],
+			name: String::new(),
 			up: unsafe {DEFAULT_UP},
 			position: Vector3::new(),
 			quaternion: Quaternion::new(),
@@ -88,10 +120,121 @@ impl Object3D {
 			receive_shadow: false,
 			frustum_culled: true,
 			render_order: 0,
-			parent: Option::None,
 		}
 	}
 
+	pub fn get_id(&self) -> usize {
+		self.id
+	}
+
+	pub fn get_uuid(&self) -> &Uuid {
+		&self.uuid
+	}
+
+	pub fn set_uuid(&mut self, uuid: Uuid) {
+		self.uuid = uuid;
+	}
+
+	pub fn get_name(&self) -> &str {
+		&self.name
+	}
+
+	pub fn set_name(&mut self, name: &str) {
+		self.name = name.to_string();
+	}
+
+	pub fn get_visible(&self) -> bool {
+		self.visible
+	}
+
+	pub fn set_visible(&mut self, visible: bool) {
+		self.visible = visible;
+	}
+
+	pub fn get_cast_shadow(&self) -> bool {
+		self.cast_shadow
+	}
+
+	pub fn set_cast_shadow(&mut self, cast_shadow: bool) {
+		self.cast_shadow = cast_shadow;
+	}
+
+	pub fn get_receive_shadow(&self) -> bool {
+		self.receive_shadow
+	}
+
+	pub fn set_receive_shadow(&mut self, receive_shadow: bool) {
+		self.receive_shadow = receive_shadow;
+	}
+
+	pub fn get_frustum_culled(&self) -> bool {
+		self.frustum_culled
+	}
+
+	pub fn set_frustum_culled(&mut self, frustum_culled: bool) {
+		self.frustum_culled = frustum_culled;
+	}
+
+	pub fn get_render_order(&self) -> u32 {
+		self.render_order
+	}
+
+	pub fn set_render_order(&mut self, render_order: u32) {
+		self.render_order = render_order;
+	}
+
+	pub fn get_matrix_auto_update(&self) -> bool {
+		self.matrix_auto_update
+	}
+
+	pub fn set_matrix_auto_update(&mut self, matrix_auto_update: bool) {
+		self.matrix_auto_update = matrix_auto_update;
+	}
+
+	pub fn get_up(&self) -> &Vector3 {
+		&self.up
+	}
+
+	pub fn get_up_mut(&mut self) -> &mut Vector3 {
+		&mut self.up
+	}
+
+	pub fn get_position(&self) -> &Vector3 {
+		&self.position
+	}
+
+	pub fn get_position_mut(&mut self) -> &mut Vector3 {
+		&mut self.position
+	}
+
+	pub fn get_quaternion(&self) -> &Quaternion {
+		&self.quaternion
+	}
+
+	pub fn get_scale(&self) -> &Vector3 {
+		&self.scale
+	}
+
+	pub fn get_scale_mut(&mut self) -> &mut Vector3 {
+		&mut self.scale
+	}
+
+	pub fn get_layers(&self) -> &Layers {
+		&self.layers
+	}
+
+	pub fn get_layers_mut(&mut self) -> &mut Layers {
+		&mut self.layers
+	}
+
+	pub fn get_matrix(&self) -> &Matrix4 {
+		&self.matrix
+	}
+
+	pub fn get_matrix_world(&self) -> &Matrix4 {
+		&self.matrix_world
+	}
+
 	pub fn apply_matrix(&mut self, matrix: &Matrix4) {
 		let m = self.matrix;
 		self.matrix.multiply_matrices(matrix, &m);
@@ -192,10 +335,11 @@ impl Object3D {
 		vector.apply_matrix4(&self.matrix_world);
 	}
 
-	pub fn world_to_local(&self, vector: &mut Vector3) {
+	pub fn world_to_local(&self, vector: &mut Vector3) -> Result<(), SingularMatrixError> {
 		let mut m1 = Matrix4::new();
-		m1.get_inverse(&self.matrix_world, false);
+		m1.get_inverse(&self.matrix_world)?;
 		vector.apply_matrix4(&m1);
+		Ok(())
 	}
 
 	pub fn look_at(&mut self, vector: &Vector3) {
@@ -204,33 +348,91 @@ impl Object3D {
 		self.quaternion.set_from_rotation_matrix(&m1);
 	}
 
-	pub fn add(parent: &Rc<RefCell<HasObject3D>>, child: &Rc<RefCell<HasObject3D>>) {
-		let weak = Rc::downgrade(parent);
-		child.borrow_mut().get_object3d_mut().parent = Some(weak);
-		parent.borrow_mut().get_object3d_mut().children.push(child.clone());
+	pub fn update_matrix(&mut self) {
+		self.matrix.compose(&self.position, &self.quaternion, &self.scale);
+		self.matrix_world_needs_update = true;
 	}
 
-	pub fn remove(&mut self, object: &Rc<RefCell<HasObject3D>>) -> bool {
-		let mut idx: Option<usize> = Option::None;
-		for (i, o) in self.children.iter().enumerate() {
-			if o.borrow().get_object3d() == object.borrow().get_object3d() {
-				idx = Some(i);
-				break;
-			}
+	// the scene owns the hierarchy, so it hands each node its parent's world matrix.
+	// returns whether the world matrix was recomputed, which forces the children too
+	pub fn update_matrix_world_from(&mut self, parent_matrix_world: Option<&Matrix4>, force: bool) -> bool {
+		if self.matrix_auto_update {
+			self.update_matrix();
 		}
-		if let Some(i) = idx {
-			self.children.swap_remove(i);
-			object.borrow_mut().get_object3d_mut().parent = Option::None;
+
+		if self.matrix_world_needs_update || force {
+			self.set_matrix_world_from(parent_matrix_world);
+			self.matrix_world_needs_update = false;
 			return true;
 		}
+
 		false
 	}
 
-	// pub fn remove_self(&mut self) -> bool {
-	// 	if self.parent.is_some() {
-	// 		true
-	// 	} else {
-	// 		false
-	// 	}
-	// }
-}
\ No newline at end of file
+	pub fn update_world_matrix_from(&mut self, parent_matrix_world: Option<&Matrix4>) {
+		if self.matrix_auto_update {
+			self.update_matrix();
+		}
+
+		self.set_matrix_world_from(parent_matrix_world);
+	}
+
+	// a three.js JSON object document for this object alone. the scene owns the hierarchy,
+	// so Scene::to_json fills in the children
+	#[cfg(feature = "serde")]
+	pub fn to_json(&self) -> Value {
+		let mut metadata = Map::new();
+		metadata.insert("version".to_string(), Value::from(4.5));
+		metadata.insert("type".to_string(), Value::from("Object"));
+		metadata.insert("generator".to_string(), Value::from("Object3D.toJSON"));
+
+		let mut document = Map::new();
+		document.insert("metadata".to_string(), Value::Object(metadata));
+		document.insert("object".to_string(), Value::Object(self.to_json_object()));
+		Value::Object(document)
+	}
+
+	// the "object" entry, leaving out flags that are at their defaults like three.js does
+	#[cfg(feature = "serde")]
+	pub fn to_json_object(&self) -> Map<String, Value> {
+		let mut object = Map::new();
+		object.insert("uuid".to_string(), Value::from(self.uuid.to_string()));
+		object.insert("type".to_string(), Value::from(self.get_type()));
+
+		if !self.name.is_empty() {
+			object.insert("name".to_string(), Value::from(self.name.clone()));
+		}
+		if self.cast_shadow {
+			object.insert("castShadow".to_string(), Value::from(true));
+		}
+		if self.receive_shadow {
+			object.insert("receiveShadow".to_string(), Value::from(true));
+		}
+		if !self.visible {
+			object.insert("visible".to_string(), Value::from(false));
+		}
+		if !self.frustum_culled {
+			object.insert("frustumCulled".to_string(), Value::from(false));
+		}
+		if self.render_order != 0 {
+			object.insert("renderOrder".to_string(), Value::from(self.render_order));
+		}
+
+		object.insert("layers".to_string(), Value::from(self.layers.mask));
+		object.insert("matrix".to_string(), Value::from(self.matrix.elements.to_vec()));
+		object.insert("up".to_string(), Value::from(vec![self.up.x, self.up.y, self.up.z]));
+
+		if !self.matrix_auto_update {
+			object.insert("matrixAutoUpdate".to_string(), Value::from(false));
+		}
+
+		object
+	}
+
+	fn set_matrix_world_from(&mut self, parent_matrix_world: Option<&Matrix4>) {
+		match parent_matrix_world {
+			Some(parent_matrix_world) => self.matrix_world.multiply_matrices(parent_matrix_world, &self.matrix),
+			None => self.matrix_world.copy(&self.matrix),
+		}
+	}
+}
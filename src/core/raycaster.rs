@@ -0,0 +1,235 @@
+use std::cmp::Ordering;
+use super::layers::Layers;
+use super::object3d::HasObject3D;
+use super::super::cameras::camera::HasCamera;
+use super::super::math::ray::Ray;
+use super::super::math::vector2::Vector2;
+use super::super::math::vector3::Vector3;
+use super::super::scenes::scene::{Scene, NodeId};
+use super::super::errors::SingularMatrixError;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Intersection {
+	pub distance: f32,
+	pub point: Vector3,
+	pub face_index: Option<usize>,
+	pub uv: Option<Vector2>,
+	pub object: NodeId,
+}
+
+// casts a world space ray into a scene. each node reports its own hits through
+// HasObject3D::raycast, the raycaster filters them by layers and near/far
+#[derive(Debug, Clone, Copy)]
+pub struct Raycaster {
+	pub ray: Ray,
+	pub near: f32,
+	pub far: f32,
+	pub layers: Layers,
+}
+
+impl Raycaster {
+	pub fn new(origin: &Vector3, direction: &Vector3, near: f32, far: f32) -> Raycaster {
+		let mut ray = Ray::new();
+		ray.set(origin, direction);
+
+		Raycaster {
+			ray,
+			near,
+			far,
+			layers: Layers::new(),
+		}
+	}
+
+	// direction is assumed to be normalized
+	pub fn set(&mut self, origin: &Vector3, direction: &Vector3) {
+		self.ray.set(origin, direction);
+	}
+
+	// coords are in normalized device coordinates, -1 to 1 on both axes
+	pub fn set_from_camera<C: HasCamera>(&mut self, coords: &Vector2, camera: &C) -> Result<(), SingularMatrixError> {
+		let matrix_world = camera.get_camera().get_object3d().get_matrix_world();
+
+		// a perspective projection has no constant w term, rays start at the eye and fan out.
+		// an orthographic one casts parallel rays from the near plane
+		if camera.get_camera().projection_matrix.elements[ 15 ] == 0.0 {
+			self.ray.origin.set_from_matrix_position(matrix_world);
+			self.ray.direction.set(coords.x, coords.y, 0.5);
+			self.ray.direction.unproject(camera)?;
+			self.ray.direction.sub(&self.ray.origin);
+			self.ray.direction.normalize();
+		} else {
+			self.ray.origin.set(coords.x, coords.y, - 1.0);
+			self.ray.origin.unproject(camera)?;
+			self.ray.direction.set(0.0, 0.0, - 1.0);
+			self.ray.direction.transform_direction(matrix_world);
+		}
+
+		Ok(())
+	}
+
+	pub fn intersect_object(&self, scene: &Scene, object: NodeId, recursive: bool) -> Vec<Intersection> {
+		let mut intersects = vec![];
+		self.intersect(scene, object, recursive, &mut intersects);
+		sort_by_distance(&mut intersects);
+		intersects
+	}
+
+	pub fn intersect_objects(&self, scene: &Scene, objects: &[NodeId], recursive: bool) -> Vec<Intersection> {
+		let mut intersects = vec![];
+		for &object in objects {
+			self.intersect(scene, object, recursive, &mut intersects);
+		}
+		sort_by_distance(&mut intersects);
+		intersects
+	}
+
+	fn intersect(&self, scene: &Scene, id: NodeId, recursive: bool, intersects: &mut Vec<Intersection>) {
+		// a node that was removed since its id was taken has nothing left to hit
+		let object = match scene.get(id) {
+			Some(object) => object,
+			None => return,
+		};
+
+		if object.get_object3d().get_layers().test(&self.layers) {
+			let first = intersects.len();
+			object.raycast(self, id, intersects);
+
+			let (near, far) = (self.near, self.far);
+			let mut i = first;
+			while i < intersects.len() {
+				if intersects[i].distance < near || intersects[i].distance > far {
+					intersects.swap_remove(i);
+				} else {
+					i += 1;
+				}
+			}
+		}
+
+		if recursive {
+			for &child in scene.get_children(id) {
+				self.intersect(scene, child, true, intersects);
+			}
+		}
+	}
+}
+
+fn sort_by_distance(intersects: &mut [Intersection]) {
+	intersects.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap_or(Ordering::Equal));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Raycaster, Intersection};
+	use super::super::object3d::{Object3D, HasObject3D};
+	use super::super::super::cameras::camera::HasCamera;
+	use super::super::super::cameras::perspective_camera::PerspectiveCamera;
+	use super::super::super::cameras::orthographic_camera::OrthographicCamera;
+	use super::super::super::math::triangle::Triangle;
+	use super::super::super::math::vector2::Vector2;
+	use super::super::super::math::vector3::Vector3;
+	use super::super::super::scenes::scene::{Scene, NodeId};
+
+	// a unit triangle in the node's local xy plane, uvs matching its x and y
+	struct TriangleNode {
+		object3d: Object3D,
+	}
+
+	impl HasObject3D for TriangleNode {
+		fn get_object3d(&self) -> &Object3D {
+			&self.object3d
+		}
+
+		fn get_object3d_mut(&mut self) -> &mut Object3D {
+			&mut self.object3d
+		}
+
+		fn raycast(&self, raycaster: &Raycaster, id: NodeId, intersects: &mut Vec<Intersection>) {
+			let mut triangle = Triangle::new();
+			triangle.set(&Vector3 { x: 0.0, y: 0.0, z: 0.0 }, &Vector3 { x: 1.0, y: 0.0, z: 0.0 }, &Vector3 { x: 0.0, y: 1.0, z: 0.0 });
+			triangle.a.apply_matrix4(self.object3d.get_matrix_world());
+			triangle.b.apply_matrix4(self.object3d.get_matrix_world());
+			triangle.c.apply_matrix4(self.object3d.get_matrix_world());
+
+			if let Some(point) = raycaster.ray.intersect_triangle(&triangle.a, &triangle.b, &triangle.c, false) {
+				intersects.push(Intersection {
+					distance: raycaster.ray.origin.distance_to(&point),
+					point,
+					face_index: Some(0),
+					uv: triangle.get_uv(&point, &Vector2 { x: 0.0, y: 0.0 }, &Vector2 { x: 1.0, y: 0.0 }, &Vector2 { x: 0.0, y: 1.0 }),
+					object: id,
+				});
+			}
+		}
+	}
+
+	fn triangle_at(z: f32) -> TriangleNode {
+		let mut object3d = Object3D::new();
+		object3d.get_position_mut().set(0.0, 0.0, z);
+		TriangleNode {
+			object3d,
+		}
+	}
+
+	#[test]
+	fn intersections_are_sorted_and_filtered() {
+		let mut scene = Scene::new();
+		let root = scene.get_root();
+		let far = scene.add(triangle_at(- 6.0));
+		let near = scene.add_to(far, triangle_at(4.0));
+		let hidden = scene.add(triangle_at(- 3.0));
+		scene.get_object3d_mut(hidden).unwrap().get_layers_mut().set(1);
+		scene.update_matrix_world(root, false);
+
+		let mut camera = PerspectiveCamera::new(50.0, 1.0, 0.1, 100.0);
+		camera.get_object3d_mut().get_position_mut().set(0.25, 0.5, 0.0);
+		camera.get_camera_mut().update_matrix_world(false);
+
+		let mut raycaster = Raycaster::new(&Vector3::new(), &Vector3 { x: 0.0, y: 0.0, z: - 1.0 }, 0.0, 100.0);
+		raycaster.set_from_camera(&Vector2 { x: 0.0, y: 0.0 }, &camera).unwrap();
+
+		let hits = raycaster.intersect_object(&scene, root, true);
+		assert_eq!(hits.iter().map(|hit| hit.object).collect::<Vec<_>>(), vec![near, far]);
+		assert!((hits[0].distance - 2.0).abs() < 1e-5);
+		let uv = hits[0].uv.unwrap();
+		assert!((uv.x - 0.25).abs() < 1e-5 && (uv.y - 0.5).abs() < 1e-5);
+
+		assert_eq!(raycaster.intersect_object(&scene, far, false).len(), 1);
+
+		raycaster.far = 5.0;
+		raycaster.layers.enable(1);
+		let hits = raycaster.intersect_objects(&scene, &[root], true);
+		assert_eq!(hits.iter().map(|hit| hit.object).collect::<Vec<_>>(), vec![near, hidden]);
+
+		scene.remove(hidden);
+		assert!(raycaster.intersect_object(&scene, hidden, true).is_empty());
+		assert_eq!(raycaster.intersect_objects(&scene, &[hidden, near], false).len(), 1);
+	}
+
+	#[test]
+	fn orthographic_cameras_cast_parallel_rays() {
+		let mut scene = Scene::new();
+		let root = scene.get_root();
+		let triangle = scene.add(triangle_at(- 5.0));
+		scene.update_matrix_world(root, false);
+
+		// the view spans 0 to 2 on both axes around the camera, looking down -z from z = 10
+		let mut camera = OrthographicCamera::new(- 1.0, 1.0, 1.0, - 1.0, 1.0, 100.0);
+		camera.get_object3d_mut().get_position_mut().set(1.0, 1.0, 10.0);
+		camera.get_camera_mut().update_matrix_world(false);
+
+		let mut raycaster = Raycaster::new(&Vector3::new(), &Vector3 { x: 0.0, y: 0.0, z: - 1.0 }, 0.0, 100.0);
+		raycaster.set_from_camera(&Vector2 { x: - 0.7, y: - 0.5 }, &camera).unwrap();
+
+		// the ray starts on the near plane above the hit, not at the camera
+		let mut offset = raycaster.ray.origin;
+		offset.sub(&Vector3 { x: 0.3, y: 0.5, z: 9.0 });
+		assert!(offset.length() < 1e-5);
+		assert!(raycaster.ray.direction.equals(&Vector3 { x: 0.0, y: 0.0, z: - 1.0 }));
+
+		let hits = raycaster.intersect_object(&scene, triangle, false);
+		assert_eq!(hits.len(), 1);
+		assert!((hits[0].distance - 14.0).abs() < 1e-5);
+		let uv = hits[0].uv.unwrap();
+		assert!((uv.x - 0.3).abs() < 1e-5 && (uv.y - 0.5).abs() < 1e-5);
+	}
+}
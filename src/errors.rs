@@ -0,0 +1,108 @@
+use std::error;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SingularMatrixError {
+	pub determinant: f64,
+}
+
+impl fmt::Display for SingularMatrixError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "can't invert matrix, determinant is {}", self.determinant)
+	}
+}
+
+impl error::Error for SingularMatrixError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorParseError {
+	pub style: String,
+}
+
+impl fmt::Display for ColorParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "unknown color {:?}", self.style)
+	}
+}
+
+impl error::Error for ColorParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectLoadError {
+	pub reason: String,
+}
+
+impl fmt::Display for ObjectLoadError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "can't load object, {}", self.reason)
+	}
+}
+
+impl error::Error for ObjectLoadError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferAttributeError {
+	pub reason: String,
+}
+
+impl fmt::Display for BufferAttributeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid buffer attribute, {}", self.reason)
+	}
+}
+
+impl error::Error for BufferAttributeError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+	SingularMatrix(SingularMatrixError),
+	ColorParse(ColorParseError),
+	ObjectLoad(ObjectLoadError),
+	BufferAttribute(BufferAttributeError),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Error::SingularMatrix(ref err) => err.fmt(f),
+			Error::ColorParse(ref err) => err.fmt(f),
+			Error::ObjectLoad(ref err) => err.fmt(f),
+			Error::BufferAttribute(ref err) => err.fmt(f),
+		}
+	}
+}
+
+impl error::Error for Error {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		match *self {
+			Error::SingularMatrix(ref err) => Some(err),
+			Error::ColorParse(ref err) => Some(err),
+			Error::ObjectLoad(ref err) => Some(err),
+			Error::BufferAttribute(ref err) => Some(err),
+		}
+	}
+}
+
+impl From<SingularMatrixError> for Error {
+	fn from(err: SingularMatrixError) -> Error {
+		Error::SingularMatrix(err)
+	}
+}
+
+impl From<ColorParseError> for Error {
+	fn from(err: ColorParseError) -> Error {
+		Error::ColorParse(err)
+	}
+}
+
+impl From<ObjectLoadError> for Error {
+	fn from(err: ObjectLoadError) -> Error {
+		Error::ObjectLoad(err)
+	}
+}
+
+impl From<BufferAttributeError> for Error {
+	fn from(err: BufferAttributeError) -> Error {
+		Error::BufferAttribute(err)
+	}
+}
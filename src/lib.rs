@@ -1,6 +1,18 @@
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+#[cfg(all(test, feature = "serde"))]
+extern crate bincode;
+
+pub mod errors;
 pub mod core;
 pub mod math;
 pub mod cameras;
+pub mod scenes;
+#[cfg(feature = "serde")]
+pub mod loaders;
 
 #[cfg(test)]
 mod tests {
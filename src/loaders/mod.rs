@@ -0,0 +1 @@
+pub mod object_loader;
\ No newline at end of file
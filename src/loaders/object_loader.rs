@@ -0,0 +1,407 @@
+extern crate uuid;
+use std::convert::TryFrom;
+use serde_json;
+use serde_json::Value;
+use self::uuid::Uuid;
+use super::super::core::object3d::{Object3D, HasObject3D};
+use super::super::cameras::camera::{Camera, View};
+use super::super::cameras::perspective_camera::PerspectiveCamera;
+use super::super::cameras::orthographic_camera::OrthographicCamera;
+use super::super::cameras::cube_camera::CubeCamera;
+use super::super::cameras::array_camera::ArrayCamera;
+use super::super::math::matrix4::Matrix4;
+use super::super::math::vector4::Vector4;
+use super::super::scenes::scene::{Scene, SceneObject, NodeId};
+use super::super::errors::ObjectLoadError;
+
+struct ParsedObject {
+	object: Box<SceneObject>,
+	children: Vec<ParsedObject>,
+}
+
+// rebuilds hierarchies from the three.js JSON object format. every node gets its uuid, name,
+// matrix, layers and flags. the camera types also get their projection, and any other type
+// (e.g. "Scene", "Group" or a mesh) loads as a plain Object3D, as three.js does for types
+// it doesn't know
+#[derive(Debug, Clone, Copy)]
+pub struct ObjectLoader {}
+
+impl ObjectLoader {
+	pub fn new() -> ObjectLoader {
+		ObjectLoader {}
+	}
+
+	pub fn parse_str(&self, text: &str, scene: &mut Scene, parent: NodeId) -> Result<NodeId, ObjectLoadError> {
+		let json: Value = serde_json::from_str(text).map_err(|err| error(&err.to_string()))?;
+		self.parse(&json, scene, parent)
+	}
+
+	// adds the document's object under parent. the scene is left untouched if any node fails to parse
+	pub fn parse(&self, json: &Value, scene: &mut Scene, parent: NodeId) -> Result<NodeId, ObjectLoadError> {
+		match json.get("metadata").and_then(|metadata| metadata.get("type")).and_then(Value::as_str) {
+			Some("Object") => {},
+			_ => return Err(error("metadata type is not \"Object\"")),
+		}
+
+		let data = json.get("object").ok_or_else(|| error("the document has no object"))?;
+		let parsed = self.parse_object(data)?;
+		Ok(self.insert(scene, parent, parsed))
+	}
+
+	fn parse_object(&self, data: &Value) -> Result<ParsedObject, ObjectLoadError> {
+		let object3d = self.parse_object3d(data)?;
+
+		let object: Box<SceneObject> = match get_str(data, "type")? {
+			Some("Camera") => {
+				let mut camera = Camera::new();
+				*camera.get_object3d_mut() = object3d;
+				Box::new(camera)
+			},
+			Some("PerspectiveCamera") => Box::new(self.parse_perspective_camera(data, object3d)?),
+			Some("OrthographicCamera") => {
+				let mut camera = OrthographicCamera::new(
+					get_float(data, "left")?.unwrap_or(- 1.0),
+					get_float(data, "right")?.unwrap_or(1.0),
+					get_float(data, "top")?.unwrap_or(1.0),
+					get_float(data, "bottom")?.unwrap_or(- 1.0),
+					get_float(data, "near")?.unwrap_or(0.1),
+					get_float(data, "far")?.unwrap_or(2000.0)
+				);
+				camera.zoom = get_float(data, "zoom")?.unwrap_or(1.0);
+				camera.view = get_view(data)?;
+				camera.update_projection_matrix();
+				*camera.get_object3d_mut() = object3d;
+				Box::new(camera)
+			},
+			Some("CubeCamera") => {
+				let mut camera = CubeCamera::new(
+					get_float(data, "near")?.unwrap_or(0.1),
+					get_float(data, "far")?.unwrap_or(2000.0)
+				);
+				*camera.get_object3d_mut() = object3d;
+				Box::new(camera)
+			},
+			Some("ArrayCamera") => {
+				let mut camera = ArrayCamera::new(vec![]);
+				*camera.get_perspective_camera_mut() = self.parse_perspective_camera(data, object3d)?;
+
+				if let Some(cameras) = data.get("cameras") {
+					let cameras = cameras.as_array().ok_or_else(|| error("cameras is not an array"))?;
+					for data in cameras {
+						let sub_camera = self.parse_perspective_camera(data, self.parse_object3d(data)?)?;
+						let viewport = get_floats(data, "viewport", 4)?.ok_or_else(|| error("a sub-camera has no viewport"))?;
+						camera.add(sub_camera, Vector4 { x: viewport[ 0 ], y: viewport[ 1 ], z: viewport[ 2 ], w: viewport[ 3 ] });
+					}
+				}
+
+				Box::new(camera)
+			},
+			_ => Box::new(object3d),
+		};
+
+		let mut children = vec![];
+		if let Some(data) = data.get("children") {
+			let data = data.as_array().ok_or_else(|| error("children is not an array"))?;
+			for child in data {
+				children.push(self.parse_object(child)?);
+			}
+		}
+
+		Ok(ParsedObject {
+			object,
+			children,
+		})
+	}
+
+	fn parse_perspective_camera(&self, data: &Value, object3d: Object3D) -> Result<PerspectiveCamera, ObjectLoadError> {
+		let mut camera = PerspectiveCamera::new(
+			get_float(data, "fov")?.unwrap_or(50.0),
+			get_float(data, "aspect")?.unwrap_or(1.0),
+			get_float(data, "near")?.unwrap_or(0.1),
+			get_float(data, "far")?.unwrap_or(2000.0)
+		);
+		camera.zoom = get_float(data, "zoom")?.unwrap_or(1.0);
+		camera.focus = get_float(data, "focus")?.unwrap_or(10.0);
+		camera.film_gauge = get_float(data, "filmGauge")?.unwrap_or(35.0);
+		camera.film_offset = get_float(data, "filmOffset")?.unwrap_or(0.0);
+		camera.view = get_view(data)?;
+		camera.update_projection_matrix();
+		*camera.get_object3d_mut() = object3d;
+		Ok(camera)
+	}
+
+	// the state every type shares
+	fn parse_object3d(&self, data: &Value) -> Result<Object3D, ObjectLoadError> {
+		let mut object = Object3D::new();
+
+		if let Some(uuid) = get_str(data, "uuid")? {
+			object.set_uuid(Uuid::parse_str(uuid).map_err(|err| error(&err.to_string()))?);
+		}
+
+		if let Some(name) = get_str(data, "name")? {
+			object.set_name(name);
+		}
+
+		if let Some(matrix) = get_floats(data, "matrix", 16)? {
+			let mut m = Matrix4::new();
+			m.copy_from_array(&matrix, None);
+			object.apply_matrix(&m);
+		}
+
+		if let Some(up) = get_floats(data, "up", 3)? {
+			object.get_up_mut().copy_from_array(&up, None);
+		}
+
+		if let Some(layers) = data.get("layers") {
+			object.get_layers_mut().mask = layers.as_u64().ok_or_else(|| error("layers is not a mask"))?;
+		}
+
+		if let Some(render_order) = data.get("renderOrder") {
+			let render_order = render_order.as_u64().ok_or_else(|| error("renderOrder is not an integer"))?;
+			object.set_render_order(u32::try_from(render_order).map_err(|_| error("renderOrder is out of range"))?);
+		}
+
+		if let Some(visible) = get_bool(data, "visible")? {
+			object.set_visible(visible);
+		}
+		if let Some(cast_shadow) = get_bool(data, "castShadow")? {
+			object.set_cast_shadow(cast_shadow);
+		}
+		if let Some(receive_shadow) = get_bool(data, "receiveShadow")? {
+			object.set_receive_shadow(receive_shadow);
+		}
+		if let Some(frustum_culled) = get_bool(data, "frustumCulled")? {
+			object.set_frustum_culled(frustum_culled);
+		}
+		if let Some(matrix_auto_update) = get_bool(data, "matrixAutoUpdate")? {
+			object.set_matrix_auto_update(matrix_auto_update);
+		}
+
+		Ok(object)
+	}
+
+	fn insert(&self, scene: &mut Scene, parent: NodeId, parsed: ParsedObject) -> NodeId {
+		let id = scene.add_boxed_to(parent, parsed.object);
+
+		for child in parsed.children {
+			self.insert(scene, id, child);
+		}
+
+		id
+	}
+}
+
+impl Default for ObjectLoader {
+	fn default() -> ObjectLoader {
+		ObjectLoader::new()
+	}
+}
+
+fn error(reason: &str) -> ObjectLoadError {
+	ObjectLoadError {
+		reason: reason.to_string(),
+	}
+}
+
+fn get_str<'a>(data: &'a Value, key: &str) -> Result<Option<&'a str>, ObjectLoadError> {
+	match data.get(key) {
+		Some(value) => value.as_str().map(Some).ok_or_else(|| error(&format!("{} is not a string", key))),
+		None => Ok(None),
+	}
+}
+
+fn get_bool(data: &Value, key: &str) -> Result<Option<bool>, ObjectLoadError> {
+	match data.get(key) {
+		Some(value) => value.as_bool().map(Some).ok_or_else(|| error(&format!("{} is not a boolean", key))),
+		None => Ok(None),
+	}
+}
+
+fn get_float(data: &Value, key: &str) -> Result<Option<f32>, ObjectLoadError> {
+	match data.get(key) {
+		Some(value) => value.as_f64().map(|v| Some(v as f32)).ok_or_else(|| error(&format!("{} is not a number", key))),
+		None => Ok(None),
+	}
+}
+
+// a camera's view offset, written by View::to_json
+fn get_view(data: &Value) -> Result<Option<View>, ObjectLoadError> {
+	let view = match data.get("view") {
+		Some(view) => view,
+		None => return Ok(None),
+	};
+
+	if get_bool(view, "enabled")? == Some(false) {
+		return Ok(None);
+	}
+
+	let field = |key: &str| get_float(view, key)?.ok_or_else(|| error(&format!("view has no {}", key)));
+
+	Ok(Some(View {
+		full_width: field("fullWidth")?,
+		full_height: field("fullHeight")?,
+		offset_x: field("offsetX")?,
+		offset_y: field("offsetY")?,
+		width: field("width")?,
+		height: field("height")?,
+	}))
+}
+
+fn get_floats(data: &Value, key: &str, len: usize) -> Result<Option<Vec<f32>>, ObjectLoadError> {
+	let value = match data.get(key) {
+		Some(value) => value,
+		None => return Ok(None),
+	};
+
+	let floats = value.as_array()
+		.filter(|array| array.len() == len)
+		.and_then(|array| array.iter().map(|v| v.as_f64().map(|v| v as f32)).collect::<Option<Vec<_>>>());
+
+	match floats {
+		Some(floats) => Ok(Some(floats)),
+		None => Err(error(&format!("{} is not an array of {} numbers", key, len))),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ObjectLoader;
+	use super::super::super::core::object3d::{Object3D, HasObject3D};
+	use super::super::super::cameras::camera::{Camera, HasCamera};
+	use super::super::super::cameras::perspective_camera::PerspectiveCamera;
+	use super::super::super::cameras::orthographic_camera::OrthographicCamera;
+	use super::super::super::cameras::cube_camera::CubeCamera;
+	use super::super::super::cameras::array_camera::ArrayCamera;
+	use super::super::super::scenes::scene::Scene;
+	use super::super::super::math::vector3::Vector3;
+	use super::super::super::math::vector4::Vector4;
+
+	#[test]
+	fn round_trips_a_hierarchy() {
+		let mut scene = Scene::new();
+		let mut parent = Object3D::new();
+		parent.set_name("parent");
+		parent.set_cast_shadow(true);
+		parent.set_visible(false);
+		parent.get_layers_mut().enable(3);
+		parent.get_position_mut().set(1.0, 2.0, 3.0);
+		parent.rotate_y(0.5);
+		parent.update_matrix();
+		let parent = scene.add(parent);
+		let mut camera = PerspectiveCamera::new(50.0, 1.0, 0.1, 100.0);
+		camera.zoom = 2.0;
+		camera.set_view_offset(200.0, 100.0, 50.0, 0.0, 100.0, 100.0);
+		let camera = scene.add_to(parent, camera);
+		scene.add_to(parent, OrthographicCamera::new(- 2.0, 2.0, 1.0, - 1.0, 0.5, 10.0));
+
+		let json = scene.to_json(parent);
+		assert_eq!(json["metadata"]["type"], "Object");
+		assert_eq!(json["object"]["children"][0]["type"], "PerspectiveCamera");
+		assert_eq!(json["object"]["children"][0]["fov"], 50.0);
+		assert_eq!(json["object"]["children"][0]["view"]["fullWidth"], 200.0);
+		assert_eq!(json["object"]["children"][1]["left"], - 2.0);
+		assert!(json["object"].get("receiveShadow").is_none());
+
+		let mut loaded = Scene::new();
+		let root = loaded.get_root();
+		let id = ObjectLoader::new().parse_str(&json.to_string(), &mut loaded, root).unwrap();
+
+		let original = scene.get_object3d(parent).unwrap();
+		let object = loaded.get_object3d(id).unwrap();
+		assert_eq!(object.get_uuid(), original.get_uuid());
+		assert_eq!(object.get_name(), "parent");
+		assert!(object.get_cast_shadow() && !object.get_visible());
+		assert_eq!(object.get_layers().mask, original.get_layers().mask);
+
+		let mut position = *object.get_position();
+		position.sub(&Vector3 { x: 1.0, y: 2.0, z: 3.0 });
+		assert!(position.length() < 1e-5);
+
+		let child = loaded.get_children(id)[ 0 ];
+		assert_eq!(loaded.get_object3d(child).unwrap().get_uuid(), scene.get_object3d(camera).unwrap().get_uuid());
+
+		let original = scene.get_as::<PerspectiveCamera>(camera).unwrap();
+		let perspective = loaded.get_as::<PerspectiveCamera>(child).unwrap();
+		assert_eq!((perspective.fov, perspective.zoom, perspective.view), (50.0, 2.0, original.view));
+		assert_eq!(perspective.get_camera().projection_matrix.elements, original.get_camera().projection_matrix.elements);
+
+		let orthographic = loaded.get_as::<OrthographicCamera>(loaded.get_children(id)[ 1 ]).unwrap();
+		assert_eq!((orthographic.left, orthographic.bottom, orthographic.near), (- 2.0, - 1.0, 0.5));
+	}
+
+	#[test]
+	fn round_trips_cube_and_array_cameras() {
+		let mut scene = Scene::new();
+		let rig = scene.add(Object3D::new());
+		let cube = scene.add_to(rig, CubeCamera::new(0.5, 50.0));
+
+		let mut array_camera = ArrayCamera::new(vec![]);
+		for i in 0..2 {
+			let mut camera = PerspectiveCamera::new(40.0, 1.0, 0.1, 10.0);
+			camera.get_object3d_mut().get_position_mut().set(i as f32, 0.0, 0.0);
+			camera.get_object3d_mut().update_matrix();
+			array_camera.add(camera, Vector4 { x: i as f32 * 256.0, y: 0.0, z: 256.0, w: 256.0 });
+		}
+		let array = scene.add_to(rig, array_camera);
+
+		let json = scene.to_json(rig);
+		assert_eq!(json["object"]["children"][0]["type"], "CubeCamera");
+		assert_eq!(json["object"]["children"][1]["cameras"][1]["viewport"][0], 256.0);
+
+		let mut loaded = Scene::new();
+		let root = loaded.get_root();
+		let id = ObjectLoader::new().parse_str(&json.to_string(), &mut loaded, root).unwrap();
+
+		let cube_camera = loaded.get_as::<CubeCamera>(loaded.get_children(id)[ 0 ]).unwrap();
+		assert_eq!(cube_camera.get_object3d().get_uuid(), scene.get_object3d(cube).unwrap().get_uuid());
+		assert!(cube_camera.get_cameras().iter().all(|camera| camera.near == 0.5 && camera.far == 50.0));
+
+		let array_camera = loaded.get_as::<ArrayCamera>(loaded.get_children(id)[ 1 ]).unwrap();
+		let original = scene.get_as::<ArrayCamera>(array).unwrap();
+		assert_eq!(array_camera.get_object3d().get_uuid(), original.get_object3d().get_uuid());
+		assert_eq!(array_camera.get_cameras().len(), 2);
+
+		for (sub_camera, original) in array_camera.get_cameras().iter().zip(original.get_cameras().iter()) {
+			assert_eq!(sub_camera.camera.get_object3d().get_uuid(), original.camera.get_object3d().get_uuid());
+			assert_eq!((sub_camera.camera.fov, sub_camera.camera.far), (40.0, 10.0));
+			assert!(sub_camera.viewport.equals(&original.viewport));
+			assert!(sub_camera.camera.get_object3d().get_position().equals(original.camera.get_object3d().get_position()));
+		}
+	}
+
+	#[test]
+	fn loads_scene_and_group_roots_as_plain_objects() {
+		let mut scene = Scene::new();
+		let root = scene.get_root();
+
+		let json = r#"{"metadata":{"type":"Object"},"object":{"type":"Scene","name":"level","children":[
+			{"type":"Group","name":"props","children":[{"type":"Mesh","name":"crate"}]},
+			{"type":"Camera","name":"eye"}
+		]}}"#;
+		let id = ObjectLoader::new().parse_str(json, &mut scene, root).unwrap();
+		assert_eq!(scene.get_node_count(), 5);
+		assert_eq!(scene.get_object3d(id).unwrap().get_name(), "level");
+
+		let group = scene.get_object_by_name(id, "props").unwrap();
+		assert!(scene.get_as::<Object3D>(group).is_some());
+		let mesh = scene.get_object_by_name(group, "crate").unwrap();
+		assert!(scene.get_as::<Object3D>(mesh).is_some());
+		let eye = scene.get_object_by_name(id, "eye").unwrap();
+		assert!(scene.get_as::<Camera>(eye).is_some());
+	}
+
+	#[test]
+	fn rejects_malformed_documents_without_touching_the_scene() {
+		let mut scene = Scene::new();
+		let root = scene.get_root();
+		let loader = ObjectLoader::new();
+
+		let bad_child = r#"{"metadata":{"type":"Object"},"object":{"type":"Object3D","children":[{"uuid":"nope"}]}}"#;
+		assert!(loader.parse_str(bad_child, &mut scene, root).is_err());
+		assert!(loader.parse_str(r#"{"object":{}}"#, &mut scene, root).is_err());
+
+		let render_order = r#"{"metadata":{"type":"Object"},"object":{"type":"Object3D","renderOrder":4294967296}}"#;
+		assert!(loader.parse_str(render_order, &mut scene, root).is_err());
+		assert_eq!(scene.get_node_count(), 1);
+	}
+}
@@ -0,0 +1,214 @@
+use super::vector2::Vector2;
+use super::matrix3::Matrix3;
+use super::float::Float;
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Box2<T = f32> {
+	pub min: Vector2<T>,
+	pub max: Vector2<T>,
+}
+
+impl<T: Float> Box2<T> {
+	pub fn new() -> Box2<T> {
+		Box2 {
+			min: Vector2 {
+				x: T::infinity(),
+				y: T::infinity(),
+			},
+			max: Vector2 {
+				x: T::neg_infinity(),
+				y: T::neg_infinity(),
+			},
+		}
+	}
+
+	pub fn get_min(&self) -> &Vector2<T> {
+		&self.min
+	}
+
+	pub fn get_max(&self) -> &Vector2<T> {
+		&self.max
+	}
+
+	pub fn set(&mut self, min: &Vector2<T>, max: &Vector2<T>) {
+		self.min.copy(min);
+		self.max.copy(max);
+	}
+
+	pub fn set_from_points(&mut self, points: &[Vector2<T>]) {
+		self.make_empty();
+
+		for point in points {
+			self.expand_by_point(point);
+		}
+	}
+
+	pub fn set_from_center_and_size(&mut self, center: &Vector2<T>, size: &Vector2<T>) {
+		let mut half_size = Vector2::new();
+		half_size.copy(size);
+		half_size.multiply_scalar(T::half());
+
+		self.min.sub_vectors(center, &half_size);
+		self.max.add_vectors(center, &half_size);
+	}
+
+	pub fn copy(&mut self, b: &Box2<T>) {
+		self.min.copy(&b.min);
+		self.max.copy(&b.max);
+	}
+
+	pub fn make_empty(&mut self) {
+		self.min.set_scalar(T::infinity());
+		self.max.set_scalar(T::neg_infinity());
+	}
+
+	pub fn is_empty(&self) -> bool {
+		// this is a more robust check for empty than ( area <= 0 ) because area can get positive with two negative axes
+		( self.max.x < self.min.x ) || ( self.max.y < self.min.y )
+	}
+
+	pub fn get_center(&self, target: &mut Vector2<T>) {
+		if self.is_empty() {
+			target.set(T::zero(), T::zero());
+		} else {
+			target.add_vectors(&self.min, &self.max);
+			target.multiply_scalar(T::half());
+		}
+	}
+
+	pub fn get_size(&self, target: &mut Vector2<T>) {
+		if self.is_empty() {
+			target.set(T::zero(), T::zero());
+		} else {
+			target.sub_vectors(&self.max, &self.min);
+		}
+	}
+
+	pub fn expand_by_point(&mut self, point: &Vector2<T>) {
+		self.min.min(point);
+		self.max.max(point);
+	}
+
+	pub fn expand_by_vector(&mut self, vector: &Vector2<T>) {
+		self.min.sub(vector);
+		self.max.add(vector);
+	}
+
+	pub fn expand_by_scalar(&mut self, scalar: T) {
+		self.min.add_scalar(- scalar);
+		self.max.add_scalar(scalar);
+	}
+
+	pub fn contains_point(&self, point: &Vector2<T>) -> bool {
+		!( point.x < self.min.x || point.x > self.max.x ||
+		   point.y < self.min.y || point.y > self.max.y )
+	}
+
+	pub fn contains_box(&self, b: &Box2<T>) -> bool {
+		self.min.x <= b.min.x && b.max.x <= self.max.x &&
+		self.min.y <= b.min.y && b.max.y <= self.max.y
+	}
+
+	pub fn get_parameter(&self, point: &Vector2<T>, target: &mut Vector2<T>) {
+		// This can potentially have a divide by zero if the box
+		// has a size dimension of 0.
+		target.set(
+			( point.x - self.min.x ) / ( self.max.x - self.min.x ),
+			( point.y - self.min.y ) / ( self.max.y - self.min.y )
+		);
+	}
+
+	pub fn intersects_box(&self, b: &Box2<T>) -> bool {
+		// using 4 splitting planes to rule out intersections
+		!( b.max.x < self.min.x || b.min.x > self.max.x ||
+		   b.max.y < self.min.y || b.min.y > self.max.y )
+	}
+
+	pub fn clamp_point(&self, point: &Vector2<T>, target: &mut Vector2<T>) {
+		target.copy(point);
+		target.clamp(&self.min, &self.max);
+	}
+
+	pub fn distance_to_point(&self, point: &Vector2<T>) -> T {
+		let mut v1 = Vector2::new();
+		self.clamp_point(point, &mut v1);
+		v1.sub(point);
+		v1.length()
+	}
+
+	pub fn intersect(&mut self, b: &Box2<T>) {
+		self.min.max(&b.min);
+		self.max.min(&b.max);
+
+		if self.is_empty() {
+			self.make_empty();
+		}
+	}
+
+	pub fn union(&mut self, b: &Box2<T>) {
+		self.min.min(&b.min);
+		self.max.max(&b.max);
+	}
+
+	// transforms the four corners, e.g. to map a region through a uv transform
+	pub fn apply_matrix3(&mut self, m: &Matrix3<T>) {
+		// transform of empty box is an empty box.
+		if self.is_empty() {
+			return;
+		}
+
+		let mut points = [Vector2::new(); 4];
+		points[ 0 ].set( self.min.x, self.min.y );
+		points[ 1 ].set( self.min.x, self.max.y );
+		points[ 2 ].set( self.max.x, self.min.y );
+		points[ 3 ].set( self.max.x, self.max.y );
+
+		for point in points.iter_mut() {
+			point.apply_matrix3(m);
+		}
+
+		self.set_from_points(&points);
+	}
+
+	pub fn translate(&mut self, offset: &Vector2<T>) {
+		self.min.add(offset);
+		self.max.add(offset);
+	}
+
+	pub fn equals(&self, b: &Box2<T>) -> bool {
+		b.min.equals(&self.min) && b.max.equals(&self.max)
+	}
+
+	pub fn cast<U: Float>(&self) -> Box2<U> {
+		Box2 {
+			min: self.min.cast(),
+			max: self.max.cast(),
+		}
+	}
+}
+
+impl<T: Float> Default for Box2<T> {
+	fn default() -> Box2<T> {
+		Box2::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Box2;
+	use super::super::vector2::Vector2;
+	use super::super::matrix3::Matrix3;
+
+	#[test]
+	fn apply_matrix3_follows_a_uv_transform() {
+		let mut b = Box2::new();
+		b.set(&Vector2 { x: 0.0, y: 0.0 }, &Vector2 { x: 1.0, y: 1.0 });
+
+		// tile twice around the center, then shift by a quarter
+		let mut m = Matrix3::new();
+		m.set_uv_transform(0.25, 0.0, 2.0, 2.0, 0.0, 0.5, 0.5);
+		b.apply_matrix3(&m);
+		assert!(b.equals(&Box2 { min: Vector2 { x: - 0.25, y: - 0.5 }, max: Vector2 { x: 1.75, y: 1.5 } }));
+	}
+}
@@ -0,0 +1,310 @@
+use super::vector3::Vector3;
+use super::matrix4::Matrix4;
+use super::sphere::Sphere;
+use super::plane::Plane;
+use super::super::scenes::scene::{Scene, NodeId};
+use super::float::Float;
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Box3<T = f32> {
+	pub min: Vector3<T>,
+	pub max: Vector3<T>,
+}
+
+impl<T: Float> Box3<T> {
+	pub fn new() -> Box3<T> {
+		Box3 {
+			min: Vector3 {
+				x: T::infinity(),
+				y: T::infinity(),
+				z: T::infinity(),
+			},
+			max: Vector3 {
+				x: T::neg_infinity(),
+				y: T::neg_infinity(),
+				z: T::neg_infinity(),
+			},
+		}
+	}
+
+	pub fn get_min(&self) -> &Vector3<T> {
+		&self.min
+	}
+
+	pub fn get_max(&self) -> &Vector3<T> {
+		&self.max
+	}
+
+	pub fn set(&mut self, min: &Vector3<T>, max: &Vector3<T>) {
+		self.min.copy(min);
+		self.max.copy(max);
+	}
+
+	pub fn set_from_array(&mut self, array: &[T]) {
+		self.make_empty();
+
+		let mut v1 = Vector3::new();
+		for i in 0..(array.len() / 3) {
+			v1.copy_from_array(array, Some(i * 3));
+			self.expand_by_point(&v1);
+		}
+	}
+
+	pub fn set_from_points(&mut self, points: &[Vector3<T>]) {
+		self.make_empty();
+
+		for point in points {
+			self.expand_by_point(point);
+		}
+	}
+
+	pub fn set_from_center_and_size(&mut self, center: &Vector3<T>, size: &Vector3<T>) {
+		let mut half_size = Vector3::new();
+		half_size.copy(size);
+		half_size.multiply_scalar(T::half());
+
+		self.min.sub_vectors(center, &half_size);
+		self.max.add_vectors(center, &half_size);
+	}
+
+	pub fn set_from_object(&mut self, scene: &Scene, object: NodeId) {
+		self.make_empty();
+		self.expand_by_object(scene, object);
+	}
+
+	pub fn copy(&mut self, b: &Box3<T>) {
+		self.min.copy(&b.min);
+		self.max.copy(&b.max);
+	}
+
+	pub fn make_empty(&mut self) {
+		self.min.set_scalar(T::infinity());
+		self.max.set_scalar(T::neg_infinity());
+	}
+
+	pub fn is_empty(&self) -> bool {
+		// this is a more robust check for empty than ( volume <= 0 ) because volume can get positive with two negative axes
+		( self.max.x < self.min.x ) || ( self.max.y < self.min.y ) || ( self.max.z < self.min.z )
+	}
+
+	pub fn get_center(&self, target: &mut Vector3<T>) {
+		if self.is_empty() {
+			target.set(T::zero(), T::zero(), T::zero());
+		} else {
+			target.add_vectors(&self.min, &self.max);
+			target.multiply_scalar(T::half());
+		}
+	}
+
+	pub fn get_size(&self, target: &mut Vector3<T>) {
+		if self.is_empty() {
+			target.set(T::zero(), T::zero(), T::zero());
+		} else {
+			target.sub_vectors(&self.max, &self.min);
+		}
+	}
+
+	pub fn expand_by_point(&mut self, point: &Vector3<T>) {
+		self.min.min(point);
+		self.max.max(point);
+	}
+
+	pub fn expand_by_vector(&mut self, vector: &Vector3<T>) {
+		self.min.sub(vector);
+		self.max.add(vector);
+	}
+
+	pub fn expand_by_scalar(&mut self, scalar: T) {
+		self.min.add_scalar(- scalar);
+		self.max.add_scalar(scalar);
+	}
+
+	// objects carry no geometry yet, so expand by the world position of the object and its descendants
+	pub fn expand_by_object(&mut self, scene: &Scene, object: NodeId) {
+		let mut v1 = Vector3::new();
+		scene.traverse(object, |_, o| {
+			v1.set_from_matrix_position(&o.get_matrix_world().cast());
+			self.expand_by_point(&v1);
+		});
+	}
+
+	pub fn contains_point(&self, point: &Vector3<T>) -> bool {
+		!( point.x < self.min.x || point.x > self.max.x ||
+		   point.y < self.min.y || point.y > self.max.y ||
+		   point.z < self.min.z || point.z > self.max.z )
+	}
+
+	pub fn contains_box(&self, b: &Box3<T>) -> bool {
+		self.min.x <= b.min.x && b.max.x <= self.max.x &&
+		self.min.y <= b.min.y && b.max.y <= self.max.y &&
+		self.min.z <= b.min.z && b.max.z <= self.max.z
+	}
+
+	pub fn get_parameter(&self, point: &Vector3<T>, target: &mut Vector3<T>) {
+		// This can potentially have a divide by zero if the box
+		// has a size dimension of 0.
+		target.set(
+			( point.x - self.min.x ) / ( self.max.x - self.min.x ),
+			( point.y - self.min.y ) / ( self.max.y - self.min.y ),
+			( point.z - self.min.z ) / ( self.max.z - self.min.z )
+		);
+	}
+
+	pub fn intersects_box(&self, b: &Box3<T>) -> bool {
+		// using 6 splitting planes to rule out intersections.
+		!( b.max.x < self.min.x || b.min.x > self.max.x ||
+		   b.max.y < self.min.y || b.min.y > self.max.y ||
+		   b.max.z < self.min.z || b.min.z > self.max.z )
+	}
+
+	pub fn intersects_sphere(&self, sphere: &Sphere<T>) -> bool {
+		let mut closest_point = Vector3::new();
+
+		// Find the point on the AABB closest to the sphere center.
+		self.clamp_point(sphere.get_center(), &mut closest_point);
+
+		// If that point is inside the sphere, the AABB and sphere intersect.
+		closest_point.distance_to_squared(sphere.get_center()) <= ( sphere.get_radius() * sphere.get_radius() )
+	}
+
+	pub fn intersects_plane(&self, plane: &Plane<T>) -> bool {
+		// We compute the minimum and maximum dot product values. If those values
+		// are on the same side (back or front) of the plane, then there is no intersection.
+
+		let normal = plane.get_normal();
+		let mut min;
+		let mut max;
+
+		if normal.x > T::zero() {
+			min = normal.x * self.min.x;
+			max = normal.x * self.max.x;
+		} else {
+			min = normal.x * self.max.x;
+			max = normal.x * self.min.x;
+		}
+
+		if normal.y > T::zero() {
+			min += normal.y * self.min.y;
+			max += normal.y * self.max.y;
+		} else {
+			min += normal.y * self.max.y;
+			max += normal.y * self.min.y;
+		}
+
+		if normal.z > T::zero() {
+			min += normal.z * self.min.z;
+			max += normal.z * self.max.z;
+		} else {
+			min += normal.z * self.max.z;
+			max += normal.z * self.min.z;
+		}
+
+		min <= - plane.get_constant() && max >= - plane.get_constant()
+	}
+
+	pub fn clamp_point(&self, point: &Vector3<T>, target: &mut Vector3<T>) {
+		target.copy(point);
+		target.clamp(&self.min, &self.max);
+	}
+
+	pub fn distance_to_point(&self, point: &Vector3<T>) -> T {
+		let mut v1 = Vector3::new();
+		self.clamp_point(point, &mut v1);
+		v1.sub(point);
+		v1.length()
+	}
+
+	pub fn get_bounding_sphere(&self, target: &mut Sphere<T>) {
+		let mut center = Vector3::new();
+		let mut size = Vector3::new();
+		self.get_center(&mut center);
+		self.get_size(&mut size);
+		target.set(&center, size.length() * T::half());
+	}
+
+	pub fn intersect(&mut self, b: &Box3<T>) {
+		self.min.max(&b.min);
+		self.max.min(&b.max);
+
+		// ensure that if there is no overlap, the result is fully empty, not slightly empty with non-inf/+inf values that will cause subsequence intersects to erroneously return valid values.
+		if self.is_empty() {
+			self.make_empty();
+		}
+	}
+
+	pub fn union(&mut self, b: &Box3<T>) {
+		self.min.min(&b.min);
+		self.max.max(&b.max);
+	}
+
+	pub fn apply_matrix4(&mut self, m: &Matrix4<T>) {
+		// transform of empty box is an empty box.
+		if self.is_empty() {
+			return;
+		}
+
+		let mut points = [Vector3::new(); 8];
+
+		// NOTE: I am using a binary pattern to specify all 2^3 combinations below
+		points[ 0 ].set( self.min.x, self.min.y, self.min.z ); // 000
+		points[ 1 ].set( self.min.x, self.min.y, self.max.z ); // 001
+		points[ 2 ].set( self.min.x, self.max.y, self.min.z ); // 010
+		points[ 3 ].set( self.min.x, self.max.y, self.max.z ); // 011
+		points[ 4 ].set( self.max.x, self.min.y, self.min.z ); // 100
+		points[ 5 ].set( self.max.x, self.min.y, self.max.z ); // 101
+		points[ 6 ].set( self.max.x, self.max.y, self.min.z ); // 110
+		points[ 7 ].set( self.max.x, self.max.y, self.max.z ); // 111
+
+		for point in points.iter_mut() {
+			point.apply_matrix4(m);
+		}
+
+		self.set_from_points(&points);
+	}
+
+	pub fn translate(&mut self, offset: &Vector3<T>) {
+		self.min.add(offset);
+		self.max.add(offset);
+	}
+
+	pub fn equals(&self, b: &Box3<T>) -> bool {
+		b.min.equals(&self.min) && b.max.equals(&self.max)
+	}
+
+	pub fn cast<U: Float>(&self) -> Box3<U> {
+		Box3 {
+			min: self.min.cast(),
+			max: self.max.cast(),
+		}
+	}
+}
+
+impl<T: Float> Default for Box3<T> {
+	fn default() -> Box3<T> {
+		Box3::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Box3;
+	use super::super::vector3::Vector3;
+	use super::super::matrix4::Matrix4;
+
+	#[test]
+	fn apply_matrix4_transforms_all_corners() {
+		let mut b = Box3::new();
+		b.set(&Vector3 { x: -1.0, y: -1.0, z: -1.0 }, &Vector3 { x: 1.0, y: 1.0, z: 1.0 });
+
+		let mut m = Matrix4::new();
+		m.make_rotation_z(::std::f32::consts::FRAC_PI_4);
+		b.apply_matrix4(&m);
+
+		let r = 2.0f32.sqrt();
+		assert!((b.max.x - r).abs() < 1e-5);
+		assert!((b.min.y + r).abs() < 1e-5);
+		assert!((b.max.z - 1.0).abs() < 1e-5);
+		assert!(b.contains_point(&Vector3 { x: 1.3, y: 0.0, z: 0.0 }));
+	}
+}
@@ -0,0 +1,488 @@
+use super::math_static::{clamp, euclidean_modulo};
+use super::super::errors::ColorParseError;
+
+const COLOR_KEYWORDS: [(&str, u32); 148] = [
+	("aliceblue", 0xF0F8FF), ("antiquewhite", 0xFAEBD7), ("aqua", 0x00FFFF), ("aquamarine", 0x7FFFD4),
+	("azure", 0xF0FFFF), ("beige", 0xF5F5DC), ("bisque", 0xFFE4C4), ("black", 0x000000),
+	("blanchedalmond", 0xFFEBCD), ("blue", 0x0000FF), ("blueviolet", 0x8A2BE2), ("brown", 0xA52A2A),
+	("burlywood", 0xDEB887), ("cadetblue", 0x5F9EA0), ("chartreuse", 0x7FFF00), ("chocolate", 0xD2691E),
+	("coral", 0xFF7F50), ("cornflowerblue", 0x6495ED), ("cornsilk", 0xFFF8DC), ("crimson", 0xDC143C),
+	("cyan", 0x00FFFF), ("darkblue", 0x00008B), ("darkcyan", 0x008B8B), ("darkgoldenrod", 0xB8860B),
+	("darkgray", 0xA9A9A9), ("darkgreen", 0x006400), ("darkgrey", 0xA9A9A9), ("darkkhaki", 0xBDB76B),
+	("darkmagenta", 0x8B008B), ("darkolivegreen", 0x556B2F), ("darkorange", 0xFF8C00), ("darkorchid", 0x9932CC),
+	("darkred", 0x8B0000), ("darksalmon", 0xE9967A), ("darkseagreen", 0x8FBC8F), ("darkslateblue", 0x483D8B),
+	("darkslategray", 0x2F4F4F), ("darkslategrey", 0x2F4F4F), ("darkturquoise", 0x00CED1), ("darkviolet", 0x9400D3),
+	("deeppink", 0xFF1493), ("deepskyblue", 0x00BFFF), ("dimgray", 0x696969), ("dimgrey", 0x696969),
+	("dodgerblue", 0x1E90FF), ("firebrick", 0xB22222), ("floralwhite", 0xFFFAF0), ("forestgreen", 0x228B22),
+	("fuchsia", 0xFF00FF), ("gainsboro", 0xDCDCDC), ("ghostwhite", 0xF8F8FF), ("gold", 0xFFD700),
+	("goldenrod", 0xDAA520), ("gray", 0x808080), ("green", 0x008000), ("greenyellow", 0xADFF2F),
+	("grey", 0x808080), ("honeydew", 0xF0FFF0), ("hotpink", 0xFF69B4), ("indianred", 0xCD5C5C),
+	("indigo", 0x4B0082), ("ivory", 0xFFFFF0), ("khaki", 0xF0E68C), ("lavender", 0xE6E6FA),
+	("lavenderblush", 0xFFF0F5), ("lawngreen", 0x7CFC00), ("lemonchiffon", 0xFFFACD), ("lightblue", 0xADD8E6),
+	("lightcoral", 0xF08080), ("lightcyan", 0xE0FFFF), ("lightgoldenrodyellow", 0xFAFAD2), ("lightgray", 0xD3D3D3),
+	("lightgreen", 0x90EE90), ("lightgrey", 0xD3D3D3), ("lightpink", 0xFFB6C1), ("lightsalmon", 0xFFA07A),
+	("lightseagreen", 0x20B2AA), ("lightskyblue", 0x87CEFA), ("lightslategray", 0x778899), ("lightslategrey", 0x778899),
+	("lightsteelblue", 0xB0C4DE), ("lightyellow", 0xFFFFE0), ("lime", 0x00FF00), ("limegreen", 0x32CD32),
+	("linen", 0xFAF0E6), ("magenta", 0xFF00FF), ("maroon", 0x800000), ("mediumaquamarine", 0x66CDAA),
+	("mediumblue", 0x0000CD), ("mediumorchid", 0xBA55D3), ("mediumpurple", 0x9370DB), ("mediumseagreen", 0x3CB371),
+	("mediumslateblue", 0x7B68EE), ("mediumspringgreen", 0x00FA9A), ("mediumturquoise", 0x48D1CC), ("mediumvioletred", 0xC71585),
+	("midnightblue", 0x191970), ("mintcream", 0xF5FFFA), ("mistyrose", 0xFFE4E1), ("moccasin", 0xFFE4B5),
+	("navajowhite", 0xFFDEAD), ("navy", 0x000080), ("oldlace", 0xFDF5E6), ("olive", 0x808000),
+	("olivedrab", 0x6B8E23), ("orange", 0xFFA500), ("orangered", 0xFF4500), ("orchid", 0xDA70D6),
+	("palegoldenrod", 0xEEE8AA), ("palegreen", 0x98FB98), ("paleturquoise", 0xAFEEEE), ("palevioletred", 0xDB7093),
+	("papayawhip", 0xFFEFD5), ("peachpuff", 0xFFDAB9), ("peru", 0xCD853F), ("pink", 0xFFC0CB),
+	("plum", 0xDDA0DD), ("powderblue", 0xB0E0E6), ("purple", 0x800080), ("rebeccapurple", 0x663399),
+	("red", 0xFF0000), ("rosybrown", 0xBC8F8F), ("royalblue", 0x4169E1), ("saddlebrown", 0x8B4513),
+	("salmon", 0xFA8072), ("sandybrown", 0xF4A460), ("seagreen", 0x2E8B57), ("seashell", 0xFFF5EE),
+	("sienna", 0xA0522D), ("silver", 0xC0C0C0), ("skyblue", 0x87CEEB), ("slateblue", 0x6A5ACD),
+	("slategray", 0x708090), ("slategrey", 0x708090), ("snow", 0xFFFAFA), ("springgreen", 0x00FF7F),
+	("steelblue", 0x4682B4), ("tan", 0xD2B48C), ("teal", 0x008080), ("thistle", 0xD8BFD8),
+	("tomato", 0xFF6347), ("turquoise", 0x40E0D0), ("violet", 0xEE82EE), ("wheat", 0xF5DEB3),
+	("white", 0xFFFFFF), ("whitesmoke", 0xF5F5F5), ("yellow", 0xFFFF00), ("yellowgreen", 0x9ACD32),
+];
+
+fn hue_to_rgb(p: f32, q: f32, t: f32) -> f32 {
+	let mut t = t;
+	if t < 0.0 {
+		t += 1.0;
+	}
+	if t > 1.0 {
+		t -= 1.0;
+	}
+	if t < 1.0 / 6.0 {
+		return p + ( q - p ) * 6.0 * t;
+	}
+	if t < 1.0 / 2.0 {
+		return q;
+	}
+	if t < 2.0 / 3.0 {
+		return p + ( q - p ) * 6.0 * ( 2.0 / 3.0 - t );
+	}
+	p
+}
+
+pub fn srgb_to_linear(c: f32) -> f32 {
+	if c < 0.04045 {
+		c * 0.077_399_38
+	} else {
+		( c * 0.947_867_3 + 0.052_132_7 ).powf(2.4)
+	}
+}
+
+pub fn linear_to_srgb(c: f32) -> f32 {
+	if c < 0.003_130_8 {
+		c * 12.92
+	} else {
+		1.055 * c.powf(0.41666) - 0.055
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hsl {
+	pub h: f32,
+	pub s: f32,
+	pub l: f32,
+}
+
+impl Hsl {
+	pub fn new() -> Hsl {
+		Hsl {
+			h: 0.0,
+			s: 0.0,
+			l: 0.0,
+		}
+	}
+}
+
+impl Default for Hsl {
+	fn default() -> Hsl {
+		Hsl::new()
+	}
+}
+
+// components are linear RGB. hex, CSS and HSL values are sRGB, as they are
+// everywhere else, and get converted on the way in and out
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Color {
+	pub r: f32,
+	pub g: f32,
+	pub b: f32,
+}
+
+impl Color {
+	pub fn new() -> Color {
+		Color {
+			r: 1.0,
+			g: 1.0,
+			b: 1.0,
+		}
+	}
+
+	pub fn from_hex(hex: u32) -> Color {
+		let mut color = Color::new();
+		color.set_hex(hex);
+		color
+	}
+
+	pub fn from_style(style: &str) -> Result<Color, ColorParseError> {
+		let mut color = Color::new();
+		color.set_style(style)?;
+		Ok(color)
+	}
+
+	pub fn set_rgb(&mut self, r: f32, g: f32, b: f32) {
+		self.r = r;
+		self.g = g;
+		self.b = b;
+	}
+
+	pub fn set_scalar(&mut self, scalar: f32) {
+		self.r = scalar;
+		self.g = scalar;
+		self.b = scalar;
+	}
+
+	pub fn set_hex(&mut self, hex: u32) {
+		self.r = ( ( hex >> 16 ) & 255 ) as f32 / 255.0;
+		self.g = ( ( hex >> 8 ) & 255 ) as f32 / 255.0;
+		self.b = ( hex & 255 ) as f32 / 255.0;
+		self.convert_srgb_to_linear();
+	}
+
+	pub fn set_hsl(&mut self, h: f32, s: f32, l: f32) {
+		// h,s,l ranges are in 0.0 - 1.0
+		let h = euclidean_modulo(h, 1.0);
+		let s = clamp(s, 0.0, 1.0);
+		let l = clamp(l, 0.0, 1.0);
+
+		if s == 0.0 {
+			self.set_scalar(l);
+		} else {
+			let p = if l <= 0.5 { l * ( 1.0 + s ) } else { l + s - ( l * s ) };
+			let q = ( 2.0 * l ) - p;
+
+			self.r = hue_to_rgb(q, p, h + 1.0 / 3.0);
+			self.g = hue_to_rgb(q, p, h);
+			self.b = hue_to_rgb(q, p, h - 1.0 / 3.0);
+		}
+
+		self.convert_srgb_to_linear();
+	}
+
+	// accepts "#rgb", "#rrggbb", "rgb(255, 0, 0)", "rgb(100%, 0%, 0%)", "hsl(120, 50%, 50%)",
+	// the rgba/hsla forms (the alpha is ignored) and the CSS color keywords
+	pub fn set_style(&mut self, style: &str) -> Result<(), ColorParseError> {
+		let error = || ColorParseError {
+			style: style.to_string(),
+		};
+		let trimmed = style.trim();
+
+		if let Some(hex) = trimmed.strip_prefix('#') {
+			// from_str_radix would also take a leading sign
+			if ! hex.chars().all(|c| c.is_ascii_hexdigit()) {
+				return Err(error());
+			}
+			let value = u32::from_str_radix(hex, 16).map_err(|_| error())?;
+
+			match hex.len() {
+				3 => {
+					let r = ( value >> 8 ) & 15;
+					let g = ( value >> 4 ) & 15;
+					let b = value & 15;
+					self.set_hex(( r * 17 ) << 16 | ( g * 17 ) << 8 | ( b * 17 ));
+				},
+				6 => self.set_hex(value),
+				_ => return Err(error()),
+			}
+
+			return Ok(());
+		}
+
+		if let (Some(open), true) = (trimmed.find('('), trimmed.ends_with(')')) {
+			let name = trimmed[ ..open ].trim().to_lowercase();
+			let args = trimmed[ open + 1 .. trimmed.len() - 1 ].split(',').map(|a| a.trim()).collect::<Vec<_>>();
+
+			if args.len() != 3 && args.len() != 4 {
+				return Err(error());
+			}
+
+			match name.as_str() {
+				"rgb" | "rgba" => {
+					let mut rgb = [ 0.0; 3 ];
+					for (component, arg) in rgb.iter_mut().zip(args.iter()) {
+						*component = match arg.strip_suffix('%') {
+							Some(percent) => parse_number(percent).ok_or_else(error)?.clamp(0.0, 100.0) / 100.0,
+							None => parse_number(arg).ok_or_else(error)?.clamp(0.0, 255.0) / 255.0,
+						};
+					}
+					self.set_rgb(rgb[ 0 ], rgb[ 1 ], rgb[ 2 ]);
+					self.convert_srgb_to_linear();
+				},
+				"hsl" | "hsla" => {
+					let h = parse_number(args[ 0 ]).ok_or_else(error)? / 360.0;
+					let s = args[ 1 ].strip_suffix('%').and_then(parse_number).ok_or_else(error)? / 100.0;
+					let l = args[ 2 ].strip_suffix('%').and_then(parse_number).ok_or_else(error)? / 100.0;
+					self.set_hsl(h, s, l);
+				},
+				_ => return Err(error()),
+			}
+
+			return Ok(());
+		}
+
+		let name = trimmed.to_lowercase();
+		match COLOR_KEYWORDS.iter().find(|&&(keyword, _)| keyword == name) {
+			Some(&(_, hex)) => {
+				self.set_hex(hex);
+				Ok(())
+			},
+			None => Err(error()),
+		}
+	}
+
+	pub fn copy(&mut self, color: &Color) {
+		self.r = color.r;
+		self.g = color.g;
+		self.b = color.b;
+	}
+
+	pub fn copy_gamma_to_linear(&mut self, color: &Color, gamma_factor: f32) {
+		self.r = color.r.powf(gamma_factor);
+		self.g = color.g.powf(gamma_factor);
+		self.b = color.b.powf(gamma_factor);
+	}
+
+	pub fn copy_linear_to_gamma(&mut self, color: &Color, gamma_factor: f32) {
+		let safe_inverse = if gamma_factor > 0.0 { 1.0 / gamma_factor } else { 1.0 };
+
+		self.r = color.r.powf(safe_inverse);
+		self.g = color.g.powf(safe_inverse);
+		self.b = color.b.powf(safe_inverse);
+	}
+
+	pub fn convert_gamma_to_linear(&mut self, gamma_factor: f32) {
+		let color = *self;
+		self.copy_gamma_to_linear(&color, gamma_factor);
+	}
+
+	pub fn convert_linear_to_gamma(&mut self, gamma_factor: f32) {
+		let color = *self;
+		self.copy_linear_to_gamma(&color, gamma_factor);
+	}
+
+	pub fn copy_srgb_to_linear(&mut self, color: &Color) {
+		self.r = srgb_to_linear(color.r);
+		self.g = srgb_to_linear(color.g);
+		self.b = srgb_to_linear(color.b);
+	}
+
+	pub fn copy_linear_to_srgb(&mut self, color: &Color) {
+		self.r = linear_to_srgb(color.r);
+		self.g = linear_to_srgb(color.g);
+		self.b = linear_to_srgb(color.b);
+	}
+
+	pub fn convert_srgb_to_linear(&mut self) {
+		let color = *self;
+		self.copy_srgb_to_linear(&color);
+	}
+
+	pub fn convert_linear_to_srgb(&mut self) {
+		let color = *self;
+		self.copy_linear_to_srgb(&color);
+	}
+
+	pub fn get_hex(&self) -> u32 {
+		let mut srgb = Color::new();
+		srgb.copy_linear_to_srgb(self);
+
+		let r = clamp(srgb.r * 255.0, 0.0, 255.0).round() as u32;
+		let g = clamp(srgb.g * 255.0, 0.0, 255.0).round() as u32;
+		let b = clamp(srgb.b * 255.0, 0.0, 255.0).round() as u32;
+		r << 16 | g << 8 | b
+	}
+
+	pub fn get_hex_string(&self) -> String {
+		format!("{:06x}", self.get_hex())
+	}
+
+	pub fn get_hsl(&self, target: &mut Hsl) {
+		// h,s,l ranges are in 0.0 - 1.0
+		let mut srgb = Color::new();
+		srgb.copy_linear_to_srgb(self);
+		let r = srgb.r;
+		let g = srgb.g;
+		let b = srgb.b;
+
+		let max = r.max(g).max(b);
+		let min = r.min(g).min(b);
+
+		let mut hue = 0.0;
+		let mut saturation = 0.0;
+		let lightness = ( min + max ) / 2.0;
+
+		if min != max {
+			let delta = max - min;
+
+			saturation = if lightness <= 0.5 { delta / ( max + min ) } else { delta / ( 2.0 - max - min ) };
+
+			if max == r {
+				hue = ( g - b ) / delta + ( if g < b { 6.0 } else { 0.0 } );
+			} else if max == g {
+				hue = ( b - r ) / delta + 2.0;
+			} else {
+				hue = ( r - g ) / delta + 4.0;
+			}
+
+			hue /= 6.0;
+		}
+
+		target.h = hue;
+		target.s = saturation;
+		target.l = lightness;
+	}
+
+	pub fn offset_hsl(&mut self, h: f32, s: f32, l: f32) {
+		let mut hsl = Hsl::new();
+		self.get_hsl(&mut hsl);
+		self.set_hsl(hsl.h + h, hsl.s + s, hsl.l + l);
+	}
+
+	pub fn add(&mut self, color: &Color) {
+		self.r += color.r;
+		self.g += color.g;
+		self.b += color.b;
+	}
+
+	pub fn add_colors(&mut self, color1: &Color, color2: &Color) {
+		self.r = color1.r + color2.r;
+		self.g = color1.g + color2.g;
+		self.b = color1.b + color2.b;
+	}
+
+	pub fn add_scalar(&mut self, s: f32) {
+		self.r += s;
+		self.g += s;
+		self.b += s;
+	}
+
+	pub fn sub(&mut self, color: &Color) {
+		self.r = ( self.r - color.r ).max(0.0);
+		self.g = ( self.g - color.g ).max(0.0);
+		self.b = ( self.b - color.b ).max(0.0);
+	}
+
+	pub fn multiply(&mut self, color: &Color) {
+		self.r *= color.r;
+		self.g *= color.g;
+		self.b *= color.b;
+	}
+
+	pub fn multiply_scalar(&mut self, s: f32) {
+		self.r *= s;
+		self.g *= s;
+		self.b *= s;
+	}
+
+	pub fn lerp(&mut self, color: &Color, alpha: f32) {
+		self.r += ( color.r - self.r ) * alpha;
+		self.g += ( color.g - self.g ) * alpha;
+		self.b += ( color.b - self.b ) * alpha;
+	}
+
+	pub fn lerp_hsl(&mut self, color: &Color, alpha: f32) {
+		let mut hsl_a = Hsl::new();
+		let mut hsl_b = Hsl::new();
+		self.get_hsl(&mut hsl_a);
+		color.get_hsl(&mut hsl_b);
+
+		let h = hsl_a.h + ( hsl_b.h - hsl_a.h ) * alpha;
+		let s = hsl_a.s + ( hsl_b.s - hsl_a.s ) * alpha;
+		let l = hsl_a.l + ( hsl_b.l - hsl_a.l ) * alpha;
+
+		self.set_hsl(h, s, l);
+	}
+
+	pub fn equals(&self, c: &Color) -> bool {
+		( c.r == self.r ) && ( c.g == self.g ) && ( c.b == self.b )
+	}
+
+	pub fn copy_from_array(&mut self, array: &[f32], offset: Option<usize>) {
+		let offset = match offset {
+			Some(off) => off,
+			None => 0usize,
+		};
+		self.r = array[offset];
+		self.g = array[offset + 1];
+		self.b = array[offset + 2];
+	}
+
+	pub fn copy_to_array(&self, array: &mut [f32], offset: Option<usize>) {
+		let offset = match offset {
+			Some(off) => off,
+			None => 0usize,
+		};
+		array[offset] = self.r;
+		array[offset + 1] = self.g;
+		array[offset + 2] = self.b;
+	}
+}
+
+impl Default for Color {
+	fn default() -> Color {
+		Color::new()
+	}
+}
+
+fn parse_number(value: &str) -> Option<f32> {
+	value.trim().parse::<f32>().ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Color, Hsl};
+
+	#[test]
+	fn parses_css_styles() {
+		assert_eq!(Color::from_style("#f80").unwrap().get_hex(), 0xff8800);
+		assert_eq!(Color::from_style("#FF8800").unwrap().get_hex(), 0xff8800);
+		assert_eq!(Color::from_style("rgb(255, 136, 0)").unwrap().get_hex(), 0xff8800);
+		assert_eq!(Color::from_style("rgba(100%, 0%, 0%, 0.5)").unwrap().get_hex(), 0xff0000);
+		assert_eq!(Color::from_style("hsl(120, 100%, 25%)").unwrap().get_hex(), 0x008000);
+		assert_eq!(Color::from_style("RebeccaPurple").unwrap().get_hex_string(), "663399");
+
+		assert_eq!(Color::from_style("rgb(-10, 300, 0)").unwrap().get_hex(), 0x00ff00);
+		assert_eq!(Color::from_style("rgb(-5%, 0%, 120%)").unwrap().get_hex(), 0x0000ff);
+
+		assert!(Color::from_style("#ff88").is_err());
+		assert!(Color::from_style("#+ff").is_err());
+		assert!(Color::from_style("rgb(1, 2)").is_err());
+		assert!(Color::from_style("not-a-color").is_err());
+	}
+
+	#[test]
+	fn stores_linear_and_converts_through_srgb() {
+		let mut color = Color::from_hex(0x808080);
+		assert!((color.r - 0.2158605).abs() < 1e-5);
+		color.convert_linear_to_srgb();
+		assert!((color.r - 128.0 / 255.0).abs() < 1e-3);
+
+		let mut hsl = Hsl::new();
+		let mut color = Color::new();
+		color.set_hsl(0.75, 0.5, 0.25);
+		color.get_hsl(&mut hsl);
+		assert!((hsl.h - 0.75).abs() < 1e-4 && (hsl.s - 0.5).abs() < 1e-4 && (hsl.l - 0.25).abs() < 1e-4);
+
+		let mut red = Color::from_hex(0xff0000);
+		red.lerp_hsl(&Color::from_hex(0x0000ff), 0.5);
+		assert_eq!(red.get_hex(), 0x00ff00);
+
+		let mut array = [ 0.0; 4 ];
+		red.copy_to_array(&mut array, Some(1));
+		let mut copy = Color::new();
+		copy.copy_from_array(&array, Some(1));
+		assert!(copy.equals(&red));
+	}
+}
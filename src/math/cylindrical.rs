@@ -0,0 +1,98 @@
+use super::vector3::Vector3;
+use super::float::Float;
+
+// theta is measured around the y axis from +z, like Spherical's equator angle
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cylindrical<T = f32> {
+	pub radius: T,
+	pub theta: T,
+	pub y: T,
+}
+
+impl<T: Float> Cylindrical<T> {
+	pub fn new() -> Cylindrical<T> {
+		Cylindrical {
+			radius: T::one(),
+			theta: T::zero(),
+			y: T::zero(),
+		}
+	}
+
+	pub fn get_radius(&self) -> T {
+		self.radius
+	}
+
+	pub fn get_theta(&self) -> T {
+		self.theta
+	}
+
+	pub fn get_y(&self) -> T {
+		self.y
+	}
+
+	pub fn set_radius(&mut self, v: T) {
+		self.radius = v;
+	}
+
+	pub fn set_theta(&mut self, v: T) {
+		self.theta = v;
+	}
+
+	pub fn set_y(&mut self, v: T) {
+		self.y = v;
+	}
+
+	pub fn set(&mut self, radius: T, theta: T, y: T) {
+		self.radius = radius;
+		self.theta = theta;
+		self.y = y;
+	}
+
+	pub fn copy(&mut self, other: &Cylindrical<T>) {
+		self.radius = other.radius;
+		self.theta = other.theta;
+		self.y = other.y;
+	}
+
+	pub fn set_from_vector3(&mut self, vec3: Vector3<T>) {
+		self.radius = ( vec3.get_x() * vec3.get_x() + vec3.get_z() * vec3.get_z() ).sqrt();
+		self.theta = vec3.get_x().atan2( vec3.get_z() );
+		self.y = vec3.get_y();
+	}
+
+	pub fn cast<U: Float>(&self) -> Cylindrical<U> {
+		Cylindrical {
+			radius: self.radius.cast(),
+			theta: self.theta.cast(),
+			y: self.y.cast(),
+		}
+	}
+}
+
+impl<T: Float> Default for Cylindrical<T> {
+	fn default() -> Cylindrical<T> {
+		Cylindrical::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Cylindrical;
+	use super::super::vector3::Vector3;
+
+	#[test]
+	fn round_trips_through_vector3() {
+		let v = Vector3 { x: 3.0, y: - 2.0, z: 4.0 };
+
+		let mut c = Cylindrical::new();
+		c.set_from_vector3(v);
+		assert_eq!(c.radius, 5.0);
+		assert_eq!(c.y, - 2.0);
+
+		let mut back = Vector3::new();
+		back.set_from_cylindrical(&c);
+		back.sub(&v);
+		assert!(back.length() < 1e-5);
+	}
+}
@@ -3,8 +3,10 @@ use super::vector3::Vector3;
 use super::quaternion::Quaternion;
 use super::math_static::clamp;
 use std::mem;
+use super::float::Float;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RotationOrders {
 	XYZ,
 	YZX,
@@ -24,40 +26,41 @@ impl From<u8> for RotationOrders {
 pub static mut DEFAULT_ORDER: RotationOrders = RotationOrders::XYZ;
 
 #[derive(Debug, Clone, Copy)]
-pub struct Euler {
-	pub x: f32,
-	pub y: f32,
-	pub z: f32,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Euler<T = f32> {
+	pub x: T,
+	pub y: T,
+	pub z: T,
 	pub order: RotationOrders,
 }
 
-impl Euler {
-	pub fn new() -> Euler {
+impl<T: Float> Euler<T> {
+	pub fn new() -> Euler<T> {
 		Euler {
-			x: 0.0f32,
-			y: 0.0f32,
-			z: 0.0f32,
+			x: T::zero(),
+			y: T::zero(),
+			z: T::zero(),
 			order: unsafe {DEFAULT_ORDER},
 		}
 	}
 
-	pub fn get_x(&self) -> f32 {
+	pub fn get_x(&self) -> T {
 		self.x
 	}
 	
-	pub fn set_x(&mut self, x: f32) {
+	pub fn set_x(&mut self, x: T) {
 		self.x = x;
 	}
 
-	pub fn get_y(&self) -> f32 {
+	pub fn get_y(&self) -> T {
 		self.y
 	}
 	
-	pub fn set_y(&mut self, y: f32) {
+	pub fn set_y(&mut self, y: T) {
 		self.y = y;
 	}
 
-	pub fn get_z(&self) -> f32 {
+	pub fn get_z(&self) -> T {
 		self.z
 	}
 
@@ -69,21 +72,21 @@ impl Euler {
 		self.order
 	}
 
-	pub fn set(&mut self, x: f32, y: f32, z: f32, order: RotationOrders) {
+	pub fn set(&mut self, x: T, y: T, z: T, order: RotationOrders) {
 		self.x = x;
 		self.y = y;
 		self.z = z;
 		self.order = order;
 	}
 
-	pub fn copy(&mut self, euler: &Euler) {
+	pub fn copy(&mut self, euler: &Euler<T>) {
 		self.x = euler.x;
 		self.y = euler.y;
 		self.z = euler.z;
 		self.order = euler.order;
 	}
 
-	pub fn set_from_rotation_matrix(&mut self, m: &Matrix4, order: Option<RotationOrders>) {
+	pub fn set_from_rotation_matrix(&mut self, m: &Matrix4<T>, order: Option<RotationOrders>) {
 		let te = m.get_elements();
 		let m11 = te[ 0 ];
 		let m12 = te[ 4 ];
@@ -101,69 +104,69 @@ impl Euler {
 
 		match order {
 			RotationOrders::XYZ => {
-				self.y = clamp(m13, -1.0, 1.0).asin();
+				self.y = clamp(m13, -T::one(), T::one()).asin();
 
-				if m13.abs() < 0.99999f32 {
+				if m13.abs() < T::from_f64(0.99999) {
 					self.x = (-m23).atan2(m33);
 					self.z = (-m12).atan2(m11);
 				} else {
 					self.x = m32.atan2(m22);
-					self.z = 0.0f32;
+					self.z = T::zero();
 				}
 			},
 			RotationOrders::YXZ => {
-				self.x = (-clamp(m23, -1.0, 1.0)).asin();
+				self.x = (-clamp(m23, -T::one(), T::one())).asin();
 
-				if m23.abs() < 0.99999f32 {
+				if m23.abs() < T::from_f64(0.99999) {
 					self.y = m13.atan2(m33);
 					self.z = m21.atan2(m22);
 				} else {
 					self.y = (-m31).atan2(m11);
-					self.z = 0.0f32;
+					self.z = T::zero();
 				}
 			},
 			RotationOrders::ZXY => {
-				self.x = clamp(m32, -1.0, 1.0).asin();
+				self.x = clamp(m32, -T::one(), T::one()).asin();
 
-				if m32.abs() < 0.99999f32 {
+				if m32.abs() < T::from_f64(0.99999) {
 					self.y = (-m31).atan2(m33);
 					self.z = (-m12).atan2(m22);
 				} else {
-					self.y = 0.0f32;
+					self.y = T::zero();
 					self.z = m21.atan2(m11);
 				}
 			},
 			RotationOrders::ZYX => {
-				self.y = (-clamp(m31, -1.0, 1.0)).asin();
+				self.y = (-clamp(m31, -T::one(), T::one())).asin();
 
-				if m31.abs() < 0.99999f32 {
+				if m31.abs() < T::from_f64(0.99999) {
 					self.x = m32.atan2(m33);
 					self.z = m21.atan2(m11);
 				} else {
-					self.x = 0.0f32;
+					self.x = T::zero();
 					self.z = (-m12).atan2(m22);
 				}
 			},
 			RotationOrders::YZX => {
-				self.z = clamp(m21, -1.0, 1.0).asin();
+				self.z = clamp(m21, -T::one(), T::one()).asin();
 
-				if m21.abs() < 0.99999f32 {
+				if m21.abs() < T::from_f64(0.99999) {
 					self.x = (-m23).atan2(m22);
 					self.y = (-m31).atan2(m11);
 				} else {
-					self.x = 0.0f32;
+					self.x = T::zero();
 					self.y = m13.atan2(m33);
 				}
 			},
 			RotationOrders::XZY => {
-				self.z = (-clamp(m21, -1.0, 1.0)).asin();
+				self.z = (-clamp(m21, -T::one(), T::one())).asin();
 
-				if m12.abs() < 0.99999f32 {
+				if m12.abs() < T::from_f64(0.99999) {
 					self.x = m32.atan2(m22);
 					self.y = m13.atan2(m11);
 				} else {
 					self.x = (-m23).atan2(m33);
-					self.y = 0.0f32;
+					self.y = T::zero();
 				}
 			},
 		}
@@ -171,13 +174,13 @@ impl Euler {
 		self.order = order;
 	}
 
-	pub fn set_from_quaternion(&mut self, q: &Quaternion, order: Option<RotationOrders>) {
+	pub fn set_from_quaternion(&mut self, q: &Quaternion<T>, order: Option<RotationOrders>) {
 		let mut matrix = Matrix4::new();
 		matrix.make_rotation_from_quaternion(q);
 		self.set_from_rotation_matrix(&matrix, order);
 	}
 
-	pub fn set_from_vector3(&mut self, v: &Vector3, order: Option<RotationOrders>) {
+	pub fn set_from_vector3(&mut self, v: &Vector3<T>, order: Option<RotationOrders>) {
 		let order = match order {
 			Some(ord) => ord,
 			None => self.order,
@@ -191,21 +194,21 @@ impl Euler {
 		self.set_from_quaternion(&q, Some(new_order));
 	}
 
-	pub fn equals(&mut self, euler: &Euler) -> bool {
+	pub fn equals(&mut self, euler: &Euler<T>) -> bool {
 		( euler.x == self.x ) && ( euler.y == self.y ) && ( euler.z == self.z ) && ( euler.order == self.order )
 	}
 
-	pub fn copy_from_array(&mut self, array: &[f32]) {
+	pub fn copy_from_array(&mut self, array: &[T]) {
 		self.x = array[0];
 		self.y = array[1];
 		self.z = array[2];
 		// I know this is bad, but its necessary to get api compatibility
 		if array.len() >= 4 {
-			self.order = RotationOrders::from(array[3] as u8);
+			self.order = RotationOrders::from(array[3].to_f64() as u8);
 		}
 	}
 
-	pub fn copy_to_array(&self, array: &mut [f32], offset: Option<usize>) {
+	pub fn copy_to_array(&self, array: &mut [T], offset: Option<usize>) {
 		let offset = match offset {
 			Some(off) => off,
 			None => 0usize,
@@ -214,10 +217,19 @@ impl Euler {
 		array[ offset + 1 ] = self.y;
 		array[ offset + 2 ] = self.z;
 		// I know this is bad, but its necessary to get api compatibility
-		array[ offset + 3 ] = (self.order as u8) as f32;
+		array[ offset + 3 ] = T::from_f64((self.order as u8) as f64);
 	}
 
-	pub fn to_vector3(&self, vector: &mut Vector3) {
+	pub fn to_vector3(&self, vector: &mut Vector3<T>) {
 		vector.set(self.x, self.y, self.z);
 	}
-}
\ No newline at end of file
+
+	pub fn cast<U: Float>(&self) -> Euler<U> {
+		Euler {
+			x: self.x.cast(),
+			y: self.y.cast(),
+			z: self.z.cast(),
+			order: self.order,
+		}
+	}
+}
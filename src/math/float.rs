@@ -0,0 +1,87 @@
+use std::fmt::Debug;
+use std::ops;
+
+pub trait Float: Copy + Debug + PartialOrd
+	+ ops::Add<Output = Self> + ops::Sub<Output = Self> + ops::Mul<Output = Self> + ops::Div<Output = Self>
+	+ ops::Rem<Output = Self> + ops::Neg<Output = Self>
+	+ ops::AddAssign + ops::SubAssign + ops::MulAssign + ops::DivAssign {
+
+	fn zero() -> Self;
+	fn one() -> Self;
+	fn two() -> Self;
+	fn half() -> Self;
+	fn pi() -> Self;
+	fn epsilon() -> Self;
+	fn infinity() -> Self;
+	fn neg_infinity() -> Self;
+
+	fn from_f64(value: f64) -> Self;
+	fn to_f64(self) -> f64;
+
+	fn sqrt(self) -> Self;
+	fn abs(self) -> Self;
+	fn sin(self) -> Self;
+	fn cos(self) -> Self;
+	fn tan(self) -> Self;
+	fn asin(self) -> Self;
+	fn acos(self) -> Self;
+	fn atan(self) -> Self;
+	fn atan2(self, other: Self) -> Self;
+	fn powf(self, n: Self) -> Self;
+	fn ln(self) -> Self;
+	fn min(self, other: Self) -> Self;
+	fn max(self, other: Self) -> Self;
+	fn floor(self) -> Self;
+	fn ceil(self) -> Self;
+	fn round(self) -> Self;
+	fn to_radians(self) -> Self;
+	fn to_degrees(self) -> Self;
+	fn is_finite(self) -> bool;
+	fn is_nan(self) -> bool;
+
+	fn cast<U: Float>(self) -> U {
+		U::from_f64(self.to_f64())
+	}
+}
+
+macro_rules! impl_float {
+	($t:ident) => {
+		impl Float for $t {
+			fn zero() -> $t { 0.0 }
+			fn one() -> $t { 1.0 }
+			fn two() -> $t { 2.0 }
+			fn half() -> $t { 0.5 }
+			fn pi() -> $t { ::std::$t::consts::PI }
+			fn epsilon() -> $t { $t::EPSILON }
+			fn infinity() -> $t { $t::INFINITY }
+			fn neg_infinity() -> $t { $t::NEG_INFINITY }
+
+			fn from_f64(value: f64) -> $t { value as $t }
+			fn to_f64(self) -> f64 { self as f64 }
+
+			fn sqrt(self) -> $t { $t::sqrt(self) }
+			fn abs(self) -> $t { $t::abs(self) }
+			fn sin(self) -> $t { $t::sin(self) }
+			fn cos(self) -> $t { $t::cos(self) }
+			fn tan(self) -> $t { $t::tan(self) }
+			fn asin(self) -> $t { $t::asin(self) }
+			fn acos(self) -> $t { $t::acos(self) }
+			fn atan(self) -> $t { $t::atan(self) }
+			fn atan2(self, other: $t) -> $t { $t::atan2(self, other) }
+			fn powf(self, n: $t) -> $t { $t::powf(self, n) }
+			fn ln(self) -> $t { $t::ln(self) }
+			fn min(self, other: $t) -> $t { $t::min(self, other) }
+			fn max(self, other: $t) -> $t { $t::max(self, other) }
+			fn floor(self) -> $t { $t::floor(self) }
+			fn ceil(self) -> $t { $t::ceil(self) }
+			fn round(self) -> $t { $t::round(self) }
+			fn to_radians(self) -> $t { $t::to_radians(self) }
+			fn to_degrees(self) -> $t { $t::to_degrees(self) }
+			fn is_finite(self) -> bool { $t::is_finite(self) }
+			fn is_nan(self) -> bool { $t::is_nan(self) }
+		}
+	}
+}
+
+impl_float!(f32);
+impl_float!(f64);
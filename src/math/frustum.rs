@@ -0,0 +1,152 @@
+use super::vector3::Vector3;
+use super::matrix4::Matrix4;
+use super::plane::Plane;
+use super::sphere::Sphere;
+use super::box3::Box3;
+use super::super::cameras::camera::HasCamera;
+use super::float::Float;
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Frustum<T = f32> {
+	pub planes: [Plane<T>; 6],
+}
+
+impl<T: Float> Frustum<T> {
+	pub fn new() -> Frustum<T> {
+		Frustum {
+			planes: [Plane::new(); 6],
+		}
+	}
+
+	pub fn get_planes(&self) -> &[Plane<T>; 6] {
+		&self.planes
+	}
+
+	pub fn set(&mut self, p0: &Plane<T>, p1: &Plane<T>, p2: &Plane<T>, p3: &Plane<T>, p4: &Plane<T>, p5: &Plane<T>) {
+		self.planes[ 0 ].copy(p0);
+		self.planes[ 1 ].copy(p1);
+		self.planes[ 2 ].copy(p2);
+		self.planes[ 3 ].copy(p3);
+		self.planes[ 4 ].copy(p4);
+		self.planes[ 5 ].copy(p5);
+	}
+
+	pub fn copy(&mut self, frustum: &Frustum<T>) {
+		for (plane, other) in self.planes.iter_mut().zip(frustum.planes.iter()) {
+			plane.copy(other);
+		}
+	}
+
+	pub fn set_from_matrix(&mut self, m: &Matrix4<T>) {
+		let me = m.get_elements();
+		let me0 = me[ 0 ];
+		let me1 = me[ 1 ];
+		let me2 = me[ 2 ];
+		let me3 = me[ 3 ];
+		let me4 = me[ 4 ];
+		let me5 = me[ 5 ];
+		let me6 = me[ 6 ];
+		let me7 = me[ 7 ];
+		let me8 = me[ 8 ];
+		let me9 = me[ 9 ];
+		let me10 = me[ 10 ];
+		let me11 = me[ 11 ];
+		let me12 = me[ 12 ];
+		let me13 = me[ 13 ];
+		let me14 = me[ 14 ];
+		let me15 = me[ 15 ];
+
+		self.planes[ 0 ].set_components( me3 - me0, me7 - me4, me11 - me8, me15 - me12 );
+		self.planes[ 1 ].set_components( me3 + me0, me7 + me4, me11 + me8, me15 + me12 );
+		self.planes[ 2 ].set_components( me3 + me1, me7 + me5, me11 + me9, me15 + me13 );
+		self.planes[ 3 ].set_components( me3 - me1, me7 - me5, me11 - me9, me15 - me13 );
+		self.planes[ 4 ].set_components( me3 - me2, me7 - me6, me11 - me10, me15 - me14 );
+		self.planes[ 5 ].set_components( me3 + me2, me7 + me6, me11 + me10, me15 + me14 );
+
+		for plane in self.planes.iter_mut() {
+			plane.normalize();
+		}
+	}
+
+	pub fn set_from_camera<C: HasCamera>(&mut self, camera: &C) {
+		let camera = camera.get_camera();
+		let mut m = Matrix4::new();
+		m.multiply_matrices(&camera.get_projection_matrix().cast(), &camera.get_matrix_world_inverse().cast());
+		self.set_from_matrix(&m);
+	}
+
+	pub fn intersects_sphere(&self, sphere: &Sphere<T>) -> bool {
+		let neg_radius = - sphere.get_radius();
+
+		for plane in self.planes.iter() {
+			if plane.distance_to_point(sphere.get_center()) < neg_radius {
+				return false;
+			}
+		}
+
+		true
+	}
+
+	pub fn intersects_box(&self, b: &Box3<T>) -> bool {
+		let mut p = Vector3::new();
+
+		for plane in self.planes.iter() {
+			let normal = plane.get_normal();
+
+			// corner at max distance
+			p.x = if normal.x > T::zero() { b.max.x } else { b.min.x };
+			p.y = if normal.y > T::zero() { b.max.y } else { b.min.y };
+			p.z = if normal.z > T::zero() { b.max.z } else { b.min.z };
+
+			if plane.distance_to_point(&p) < T::zero() {
+				return false;
+			}
+		}
+
+		true
+	}
+
+	pub fn contains_point(&self, point: &Vector3<T>) -> bool {
+		for plane in self.planes.iter() {
+			if plane.distance_to_point(point) < T::zero() {
+				return false;
+			}
+		}
+
+		true
+	}
+}
+
+impl<T: Float> Default for Frustum<T> {
+	fn default() -> Frustum<T> {
+		Frustum::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Frustum;
+	use super::super::vector3::Vector3;
+	use super::super::sphere::Sphere;
+	use super::super::matrix4::Matrix4;
+
+	#[test]
+	fn set_from_matrix_culls_against_perspective() {
+		let mut m = Matrix4::new();
+		m.make_frustum(-1.0, 1.0, -1.0, 1.0, 1.0, 100.0);
+
+		let mut frustum = Frustum::new();
+		frustum.set_from_matrix(&m);
+
+		assert!(frustum.contains_point(&Vector3 { x: 0.0, y: 0.0, z: -50.0 }));
+		assert!(!frustum.contains_point(&Vector3 { x: 0.0, y: 0.0, z: 50.0 }));
+		assert!(!frustum.contains_point(&Vector3 { x: 10.0, y: 0.0, z: -5.0 }));
+
+		let mut sphere = Sphere::new();
+		sphere.set(&Vector3 { x: 10.0, y: 0.0, z: -5.0 }, 6.0);
+		assert!(frustum.intersects_sphere(&sphere));
+		sphere.set(&Vector3 { x: 10.0, y: 0.0, z: -5.0 }, 1.0);
+		assert!(!frustum.intersects_sphere(&sphere));
+	}
+}
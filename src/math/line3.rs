@@ -0,0 +1,231 @@
+use super::vector3::Vector3;
+use super::matrix4::Matrix4;
+use super::math_static::clamp;
+use super::float::Float;
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Line3<T = f32> {
+	pub start: Vector3<T>,
+	pub end: Vector3<T>,
+}
+
+impl<T: Float> Line3<T> {
+	pub fn new() -> Line3<T> {
+		Line3 {
+			start: Vector3 {
+				x: T::zero(),
+				y: T::zero(),
+				z: T::zero(),
+			},
+			end: Vector3 {
+				x: T::zero(),
+				y: T::zero(),
+				z: T::zero(),
+			},
+		}
+	}
+
+	pub fn get_start(&self) -> &Vector3<T> {
+		&self.start
+	}
+
+	pub fn get_end(&self) -> &Vector3<T> {
+		&self.end
+	}
+
+	pub fn set(&mut self, start: &Vector3<T>, end: &Vector3<T>) {
+		self.start.copy(start);
+		self.end.copy(end);
+	}
+
+	pub fn copy(&mut self, line: &Line3<T>) {
+		self.start.copy(&line.start);
+		self.end.copy(&line.end);
+	}
+
+	pub fn get_center(&self, target: &mut Vector3<T>) {
+		target.add_vectors(&self.start, &self.end);
+		target.multiply_scalar(T::half());
+	}
+
+	pub fn delta(&self, target: &mut Vector3<T>) {
+		target.sub_vectors(&self.end, &self.start);
+	}
+
+	pub fn distance_sq(&self) -> T {
+		self.start.distance_to_squared(&self.end)
+	}
+
+	pub fn distance(&self) -> T {
+		self.start.distance_to(&self.end)
+	}
+
+	pub fn at(&self, t: T, target: &mut Vector3<T>) {
+		self.delta(target);
+		target.multiply_scalar(t);
+		target.add(&self.start);
+	}
+
+	// t is 0 at start and 1 at end. unclamped, the parameter is for the infinite line
+	pub fn closest_point_to_point_parameter(&self, point: &Vector3<T>, clamp_to_line: bool) -> T {
+		let mut start_p = Vector3::new();
+		let mut start_end = Vector3::new();
+		start_p.sub_vectors(point, &self.start);
+		start_end.sub_vectors(&self.end, &self.start);
+
+		let start_end2 = start_end.dot(&start_end);
+		// a zero length line has every parameter at the same point
+		if start_end2 == T::zero() {
+			return T::zero();
+		}
+
+		let t = start_end.dot(&start_p) / start_end2;
+
+		if clamp_to_line {
+			clamp(t, T::zero(), T::one())
+		} else {
+			t
+		}
+	}
+
+	pub fn closest_point_to_point(&self, point: &Vector3<T>, clamp_to_line: bool, target: &mut Vector3<T>) {
+		let t = self.closest_point_to_point_parameter(point, clamp_to_line);
+		self.at(t, target);
+	}
+
+	pub fn apply_matrix4(&mut self, matrix: &Matrix4<T>) {
+		self.start.apply_matrix4(matrix);
+		self.end.apply_matrix4(matrix);
+	}
+
+	pub fn equals(&self, line: &Line3<T>) -> bool {
+		line.start.equals(&self.start) && line.end.equals(&self.end)
+	}
+
+	pub fn cast<U: Float>(&self) -> Line3<U> {
+		Line3 {
+			start: self.start.cast(),
+			end: self.end.cast(),
+		}
+	}
+}
+
+impl<T: Float> Default for Line3<T> {
+	fn default() -> Line3<T> {
+		Line3::new()
+	}
+}
+
+impl From<Line3<f32>> for Line3<f64> {
+	fn from(line: Line3<f32>) -> Line3<f64> {
+		line.cast()
+	}
+}
+
+// closest points between two segments, from "Real-Time Collision Detection" by Christer Ericson.
+// sets c1 on line1 and c2 on line2, and returns the squared distance between them
+pub fn closest_points_between_segments<T: Float>(line1: &Line3<T>, line2: &Line3<T>, c1: &mut Vector3<T>, c2: &mut Vector3<T>) -> T {
+	let mut d1 = Vector3::new();
+	let mut d2 = Vector3::new();
+	let mut r = Vector3::new();
+	line1.delta(&mut d1);
+	line2.delta(&mut d2);
+	r.sub_vectors(&line1.start, &line2.start);
+
+	let a = d1.dot(&d1);
+	let e = d2.dot(&d2);
+	let f = d2.dot(&r);
+	let epsilon = T::epsilon();
+
+	let s;
+	let t;
+
+	if a <= epsilon && e <= epsilon {
+		// both segments degenerate into points
+		s = T::zero();
+		t = T::zero();
+	} else if a <= epsilon {
+		// first segment degenerates into a point
+		s = T::zero();
+		t = clamp(f / e, T::zero(), T::one());
+	} else {
+		let c = d1.dot(&r);
+
+		if e <= epsilon {
+			// second segment degenerates into a point
+			t = T::zero();
+			s = clamp(- c / a, T::zero(), T::one());
+		} else {
+			let b = d1.dot(&d2);
+			let denom = a * e - b * b;
+
+			// for parallel segments pick an arbitrary s and let t follow
+			let s0 = if denom != T::zero() {
+				clamp(( b * f - c * e ) / denom, T::zero(), T::one())
+			} else {
+				T::zero()
+			};
+			let t0 = ( b * s0 + f ) / e;
+
+			if t0 < T::zero() {
+				t = T::zero();
+				s = clamp(- c / a, T::zero(), T::one());
+			} else if t0 > T::one() {
+				t = T::one();
+				s = clamp(( b - c ) / a, T::zero(), T::one());
+			} else {
+				t = t0;
+				s = s0;
+			}
+		}
+	}
+
+	c1.copy(&line1.start);
+	c1.add_scaled_vector(&d1, s);
+	c2.copy(&line2.start);
+	c2.add_scaled_vector(&d2, t);
+
+	c1.distance_to_squared(c2)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Line3, closest_points_between_segments};
+	use super::super::vector3::Vector3;
+
+	#[test]
+	fn closest_point_parameter_clamps_to_the_segment() {
+		let mut line = Line3::new();
+		line.set(&Vector3 { x: 0.0, y: 0.0, z: 0.0 }, &Vector3 { x: 4.0, y: 0.0, z: 0.0 });
+		assert_eq!(line.distance(), 4.0);
+
+		let point = Vector3 { x: 6.0, y: 1.0, z: 0.0 };
+		assert_eq!(line.closest_point_to_point_parameter(&point, false), 1.5);
+		assert_eq!(line.closest_point_to_point_parameter(&point, true), 1.0);
+
+		let mut target = Vector3::new();
+		line.closest_point_to_point(&Vector3 { x: 1.0, y: 3.0, z: 0.0 }, true, &mut target);
+		assert!(target.equals(&Vector3 { x: 1.0, y: 0.0, z: 0.0 }));
+	}
+
+	#[test]
+	fn closest_points_between_crossing_and_disjoint_segments() {
+		let mut line1 = Line3::new();
+		let mut line2 = Line3::new();
+		let mut c1 = Vector3::new();
+		let mut c2 = Vector3::new();
+
+		line1.set(&Vector3 { x: - 1.0, y: 0.0, z: 0.0 }, &Vector3 { x: 1.0, y: 0.0, z: 0.0 });
+		line2.set(&Vector3 { x: 0.0, y: - 1.0, z: 2.0 }, &Vector3 { x: 0.0, y: 1.0, z: 2.0 });
+		assert_eq!(closest_points_between_segments(&line1, &line2, &mut c1, &mut c2), 4.0);
+		assert!(c1.equals(&Vector3 { x: 0.0, y: 0.0, z: 0.0 }));
+		assert!(c2.equals(&Vector3 { x: 0.0, y: 0.0, z: 2.0 }));
+
+		// the infinite lines would cross at x = 3, past the end of both segments
+		line2.set(&Vector3 { x: 3.0, y: 1.0, z: 0.0 }, &Vector3 { x: 3.0, y: 2.0, z: 0.0 });
+		assert_eq!(closest_points_between_segments(&line1, &line2, &mut c1, &mut c2), 5.0);
+		assert!(c1.equals(&Vector3 { x: 1.0, y: 0.0, z: 0.0 }));
+		assert!(c2.equals(&Vector3 { x: 3.0, y: 1.0, z: 0.0 }));
+	}
+}
@@ -1,28 +1,33 @@
 use super::matrix4::Matrix4;
 use super::vector3::Vector3;
+use std::ops;
+use super::float::Float;
+use super::super::errors::SingularMatrixError;
 
 #[derive(Debug, Clone, Copy)]
-pub struct Matrix3 {
-	pub elements: [f32; 9] 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Matrix3<T = f32> {
+	pub elements: [T; 9] 
 }
 
-impl Matrix3 {
-	pub fn new() -> Matrix3 {
+impl<T: Float> Matrix3<T> {
+	pub fn new() -> Matrix3<T> {
 		let elements = [
-			1.0, 0.0, 0.0,
-			0.0, 1.0, 0.0,
-			0.0, 0.0, 1.0,
+			T::one(), T::zero(), T::zero(),
+			T::zero(), T::one(), T::zero(),
+			T::zero(), T::zero(), T::one(),
 		];
 		Matrix3 {
 			elements: elements
 		}
 	}
 
-	pub fn get_elements(&self) -> &[f32; 9] {
+	pub fn get_elements(&self) -> &[T; 9] {
 		&self.elements
 	}
 
-	pub fn set(&mut self, n11: f32, n12: f32, n13: f32, n21: f32, n22: f32, n23: f32, n31: f32, n32: f32, n33: f32) {
+	pub fn set(&mut self, n11: T, n12: T, n13: T, n21: T, n22: T, n23: T, n31: T, n32: T, n33: T) {
 		self.elements[ 0 ] = n11;
 		self.elements[ 1 ] = n21;
 		self.elements[ 2 ] = n31;
@@ -38,13 +43,13 @@ impl Matrix3 {
 
 	pub fn identity(&mut self) {
 		self.set(
-			1.0, 0.0, 0.0,
-			0.0, 1.0, 0.0,
-			0.0, 0.0, 1.0
+			T::one(), T::zero(), T::zero(),
+			T::zero(), T::one(), T::zero(),
+			T::zero(), T::zero(), T::one()
 		);
 	}
 
-	pub fn copy(&mut self, m: &Matrix3) {
+	pub fn copy(&mut self, m: &Matrix3<T>) {
 		self.set(
 			m.elements[ 0 ], m.elements[ 3 ], m.elements[ 6 ],
 			m.elements[ 1 ], m.elements[ 4 ], m.elements[ 7 ],
@@ -52,7 +57,7 @@ impl Matrix3 {
 		);
 	}
 
-	pub fn set_from_matrix4(&mut self, m: &Matrix4) {
+	pub fn set_from_matrix4(&mut self, m: &Matrix4<T>) {
 		let me = m.get_elements();
 		self.set(
 			me[ 0 ], me[ 4 ], me[  8 ],
@@ -61,7 +66,7 @@ impl Matrix3 {
 		);
 	}
 
-	pub fn apply_to_vector3_array(&self, array: &mut [f32], offset: Option<usize>, length: Option<usize>) {
+	pub fn apply_to_vector3_array(&self, array: &mut [T], offset: Option<usize>, length: Option<usize>) {
 		let mut v1 = Vector3::new();
 		let offset: usize = match offset {
 			Some(off) => off,
@@ -81,7 +86,51 @@ impl Matrix3 {
 		}
 	}
 
-	pub fn multiply_scalar(&mut self, s: f32) {
+	pub fn multiply(&mut self, m: &Matrix3<T>) {
+		let s = *self;
+		self.multiply_matrices(&s, m);
+	}
+
+	pub fn premultiply(&mut self, m: &Matrix3<T>) {
+		let s = *self;
+		self.multiply_matrices(m, &s);
+	}
+
+	pub fn multiply_matrices(&mut self, a: &Matrix3<T>, b: &Matrix3<T>) {
+		let a11 = a.elements[ 0 ];
+		let a12 = a.elements[ 3 ];
+		let a13 = a.elements[ 6 ];
+		let a21 = a.elements[ 1 ];
+		let a22 = a.elements[ 4 ];
+		let a23 = a.elements[ 7 ];
+		let a31 = a.elements[ 2 ];
+		let a32 = a.elements[ 5 ];
+		let a33 = a.elements[ 8 ];
+
+		let b11 = b.elements[ 0 ];
+		let b12 = b.elements[ 3 ];
+		let b13 = b.elements[ 6 ];
+		let b21 = b.elements[ 1 ];
+		let b22 = b.elements[ 4 ];
+		let b23 = b.elements[ 7 ];
+		let b31 = b.elements[ 2 ];
+		let b32 = b.elements[ 5 ];
+		let b33 = b.elements[ 8 ];
+
+		self.elements[ 0 ] = a11 * b11 + a12 * b21 + a13 * b31;
+		self.elements[ 3 ] = a11 * b12 + a12 * b22 + a13 * b32;
+		self.elements[ 6 ] = a11 * b13 + a12 * b23 + a13 * b33;
+
+		self.elements[ 1 ] = a21 * b11 + a22 * b21 + a23 * b31;
+		self.elements[ 4 ] = a21 * b12 + a22 * b22 + a23 * b32;
+		self.elements[ 7 ] = a21 * b13 + a22 * b23 + a23 * b33;
+
+		self.elements[ 2 ] = a31 * b11 + a32 * b21 + a33 * b31;
+		self.elements[ 5 ] = a31 * b12 + a32 * b22 + a33 * b32;
+		self.elements[ 8 ] = a31 * b13 + a32 * b23 + a33 * b33;
+	}
+
+	pub fn multiply_scalar(&mut self, s: T) {
 		self.elements[ 0 ] *= s;
 		self.elements[ 3 ] *= s;
 		self.elements[ 6 ] *= s;
@@ -95,7 +144,60 @@ impl Matrix3 {
 		self.elements[ 8 ] *= s;
 	}
 
-	pub fn determinant(&self) -> f32 {
+	// builds a 2D affine transform for texture coordinates: scale and rotate
+	// around the center (cx, cy), then offset by (tx, ty)
+	pub fn set_uv_transform(&mut self, tx: T, ty: T, sx: T, sy: T, rotation: T, cx: T, cy: T) {
+		let c = rotation.cos();
+		let s = rotation.sin();
+
+		self.set(
+			sx * c, sx * s, - sx * ( c * cx + s * cy ) + cx + tx,
+			- sy * s, sy * c, - sy * ( - s * cx + c * cy ) + cy + ty,
+			T::zero(), T::zero(), T::one()
+		);
+	}
+
+	pub fn scale(&mut self, sx: T, sy: T) {
+		self.elements[ 0 ] *= sx;
+		self.elements[ 3 ] *= sx;
+		self.elements[ 6 ] *= sx;
+
+		self.elements[ 1 ] *= sy;
+		self.elements[ 4 ] *= sy;
+		self.elements[ 7 ] *= sy;
+	}
+
+	pub fn rotate(&mut self, theta: T) {
+		let c = theta.cos();
+		let s = theta.sin();
+
+		let a11 = self.elements[ 0 ];
+		let a12 = self.elements[ 3 ];
+		let a13 = self.elements[ 6 ];
+		let a21 = self.elements[ 1 ];
+		let a22 = self.elements[ 4 ];
+		let a23 = self.elements[ 7 ];
+
+		self.elements[ 0 ] = c * a11 + s * a21;
+		self.elements[ 3 ] = c * a12 + s * a22;
+		self.elements[ 6 ] = c * a13 + s * a23;
+
+		self.elements[ 1 ] = - s * a11 + c * a21;
+		self.elements[ 4 ] = - s * a12 + c * a22;
+		self.elements[ 7 ] = - s * a13 + c * a23;
+	}
+
+	pub fn translate(&mut self, tx: T, ty: T) {
+		self.elements[ 0 ] += tx * self.elements[ 2 ];
+		self.elements[ 3 ] += tx * self.elements[ 5 ];
+		self.elements[ 6 ] += tx * self.elements[ 8 ];
+
+		self.elements[ 1 ] += ty * self.elements[ 2 ];
+		self.elements[ 4 ] += ty * self.elements[ 5 ];
+		self.elements[ 7 ] += ty * self.elements[ 8 ];
+	}
+
+	pub fn determinant(&self) -> T {
 		let a = self.elements[ 0 ];
 		let b = self.elements[ 1 ];
 		let c = self.elements[ 2 ];
@@ -109,7 +211,7 @@ impl Matrix3 {
 		a * e * i - a * f * h - b * d * i + b * f * g + c * d * h - c * e * g
 	}
 
-	pub fn get_inverse(&mut self, matrix: &Matrix3, throw_on_degenerate: bool) {
+	pub fn get_inverse(&mut self, matrix: &Matrix3<T>) -> Result<(), SingularMatrixError> {
 		let me = matrix.elements;
 		let n11 = me[ 0 ];
 		let n21 = me[ 1 ];
@@ -129,20 +231,14 @@ impl Matrix3 {
 
 		let det = n11 * t11 + n21 * t12 + n31 * t13;
 
-		if det == 0.0 {
-			let msg = "Matrix3.getInverse(): can't invert matrix, determinant is 0";
-
-			if throw_on_degenerate {
-				panic!("{}", msg);
-			} else {
-				println!("{}", msg);
-			}
+		let det_inv = T::one() / det;
 
-			return self.identity();
+		if det == T::zero() || !det_inv.is_finite() {
+			return Err(SingularMatrixError {
+				determinant: det.to_f64(),
+			});
 		}
 
-		let det_inv = 1.0 / det;
-
 		self.elements[ 0 ] = t11 * det_inv;
 		self.elements[ 1 ] = ( n31 * n23 - n33 * n21 ) * det_inv;
 		self.elements[ 2 ] = ( n32 * n21 - n31 * n22 ) * det_inv;
@@ -154,10 +250,18 @@ impl Matrix3 {
 		self.elements[ 6 ] = t13 * det_inv;
 		self.elements[ 7 ] = ( n21 * n13 - n23 * n11 ) * det_inv;
 		self.elements[ 8 ] = ( n22 * n11 - n21 * n12 ) * det_inv;
+
+		Ok(())
+	}
+
+	pub fn try_inverse(&self) -> Result<Matrix3<T>, SingularMatrixError> {
+		let mut m = Matrix3::new();
+		m.get_inverse(self)?;
+		Ok(m)
 	}
 
 	pub fn transpose(&mut self) {
-		let mut tmp: f32;
+		let mut tmp: T;
 		tmp = self.elements[ 1 ];
 		self.elements[ 1 ] = self.elements[ 3 ];
 		self.elements[ 3 ] = tmp;
@@ -171,14 +275,15 @@ impl Matrix3 {
 		self.elements[ 7 ] = tmp;
 	}
 
-	pub fn get_normal_matrix(&mut self, matrix4: &Matrix4) {
+	pub fn get_normal_matrix(&mut self, matrix4: &Matrix4<T>) -> Result<(), SingularMatrixError> {
 		self.set_from_matrix4(matrix4);
 		let s = *self;
-		self.get_inverse(&s, false);
+		self.get_inverse(&s)?;
 		self.transpose();
+		Ok(())
 	}
 
-	pub fn transpose_into_array(&self, r: &mut [f32]) {
+	pub fn transpose_into_array(&self, r: &mut [T]) {
 		r[ 0 ] = self.elements[ 0 ];
 		r[ 1 ] = self.elements[ 3 ];
 		r[ 2 ] = self.elements[ 6 ];
@@ -190,11 +295,11 @@ impl Matrix3 {
 		r[ 8 ] = self.elements[ 8 ];
 	}
 
-	pub fn copy_from_array(&mut self, array: &[f32]) {
+	pub fn copy_from_array(&mut self, array: &[T]) {
 		self.elements.copy_from_slice(array);
 	}
 
-	pub fn copy_to_array(&self, array: &mut [f32], offset: Option<usize>) {
+	pub fn copy_to_array(&self, array: &mut [T], offset: Option<usize>) {
 		let offset: usize = match offset {
 			Some(off) => off,
 			None => 0,
@@ -212,4 +317,217 @@ impl Matrix3 {
 		array[ offset + 7 ] = self.elements[ 7 ];
 		array[ offset + 8 ]  = self.elements[ 8 ];
 	}
-}
\ No newline at end of file
+
+	pub fn cast<U: Float>(&self) -> Matrix3<U> {
+		let mut m = Matrix3::new();
+		for (dst, src) in m.elements.iter_mut().zip(self.elements.iter()) {
+			*dst = src.cast();
+		}
+		m
+	}
+}
+
+impl From<Matrix3<f32>> for Matrix3<f64> {
+	fn from(m: Matrix3<f32>) -> Matrix3<f64> {
+		m.cast()
+	}
+}
+
+// addition, subtraction and negation work element by element
+impl<T: Float> ops::Add<Matrix3<T>> for Matrix3<T> {
+	type Output = Matrix3<T>;
+
+	fn add(self, rhs: Matrix3<T>) -> Matrix3<T> {
+		let mut m = self;
+		m += rhs;
+		m
+	}
+}
+
+impl<T: Float> ops::AddAssign<Matrix3<T>> for Matrix3<T> {
+	fn add_assign(&mut self, rhs: Matrix3<T>) {
+		for (e, r) in self.elements.iter_mut().zip(rhs.elements.iter()) {
+			*e += *r;
+		}
+	}
+}
+
+impl<T: Float> ops::Sub<Matrix3<T>> for Matrix3<T> {
+	type Output = Matrix3<T>;
+
+	fn sub(self, rhs: Matrix3<T>) -> Matrix3<T> {
+		let mut m = self;
+		m -= rhs;
+		m
+	}
+}
+
+impl<T: Float> ops::SubAssign<Matrix3<T>> for Matrix3<T> {
+	fn sub_assign(&mut self, rhs: Matrix3<T>) {
+		for (e, r) in self.elements.iter_mut().zip(rhs.elements.iter()) {
+			*e -= *r;
+		}
+	}
+}
+
+impl<T: Float> ops::Neg for Matrix3<T> {
+	type Output = Matrix3<T>;
+
+	fn neg(self) -> Matrix3<T> {
+		let mut m = self;
+		for e in m.elements.iter_mut() {
+			*e = - *e;
+		}
+		m
+	}
+}
+
+impl<T: Float> ops::Mul<Matrix3<T>> for Matrix3<T> {
+	type Output = Matrix3<T>;
+
+	fn mul(self, rhs: Matrix3<T>) -> Matrix3<T> {
+		let mut m = Matrix3::new();
+		m.multiply_matrices(&self, &rhs);
+		m
+	}
+}
+
+impl<T: Float> ops::MulAssign<Matrix3<T>> for Matrix3<T> {
+	fn mul_assign(&mut self, rhs: Matrix3<T>) {
+		self.multiply(&rhs);
+	}
+}
+
+impl<T: Float> ops::Mul<T> for Matrix3<T> {
+	type Output = Matrix3<T>;
+
+	fn mul(self, rhs: T) -> Matrix3<T> {
+		let mut m = self;
+		m.multiply_scalar(rhs);
+		m
+	}
+}
+
+impl<T: Float> ops::MulAssign<T> for Matrix3<T> {
+	fn mul_assign(&mut self, rhs: T) {
+		self.multiply_scalar(rhs);
+	}
+}
+
+impl<T: Float> ops::Mul<Vector3<T>> for Matrix3<T> {
+	type Output = Vector3<T>;
+
+	fn mul(self, rhs: Vector3<T>) -> Vector3<T> {
+		let mut v = rhs;
+		v.apply_matrix3(&self);
+		v
+	}
+}
+
+// elements are stored column-major, so `m[i]` indexes the flat array
+impl<T: Float> ops::Index<usize> for Matrix3<T> {
+	type Output = T;
+
+	fn index(&self, index: usize) -> &T {
+		&self.elements[ index ]
+	}
+}
+
+impl<T: Float> ops::IndexMut<usize> for Matrix3<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T {
+		&mut self.elements[ index ]
+	}
+}
+
+// `m[(row, column)]` follows the row-major order of `set`
+impl<T: Float> ops::Index<(usize, usize)> for Matrix3<T> {
+	type Output = T;
+
+	fn index(&self, index: (usize, usize)) -> &T {
+		&self.elements[ index.1 * 3 + index.0 ]
+	}
+}
+
+impl<T: Float> ops::IndexMut<(usize, usize)> for Matrix3<T> {
+	fn index_mut(&mut self, index: (usize, usize)) -> &mut T {
+		&mut self.elements[ index.1 * 3 + index.0 ]
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Matrix3;
+	use super::super::vector2::Vector2;
+	use super::super::vector3::Vector3;
+
+	#[test]
+	fn operators_match_methods() {
+		let mut a: Matrix3 = Matrix3::new();
+		a.set(1.0, 2.0, 3.0, 0.0, 1.0, 4.0, 5.0, 6.0, 0.0);
+		let mut b = Matrix3::new();
+		b.set(0.5, 0.0, - 1.0, 2.0, 1.5, 0.0, 0.25, 3.0, 1.0);
+
+		let mut expected = a;
+		expected.multiply(&b);
+		assert_eq!((a * b).elements, expected.elements);
+		let mut m = a;
+		m *= b;
+		assert_eq!(m.elements, expected.elements);
+
+		expected = a;
+		expected.multiply_scalar(2.0);
+		assert_eq!((a * 2.0).elements, expected.elements);
+		m = a;
+		m *= 2.0;
+		assert_eq!(m.elements, expected.elements);
+
+		let v = Vector3 { x: 1.0, y: - 2.0, z: 0.5 };
+		let mut applied = v;
+		applied.apply_matrix3(&a);
+		assert!((a * v).equals(&applied));
+
+		// a + a is a * 2, a - a is all zeros and - a is a * -1
+		assert_eq!((a + a).elements, expected.elements);
+		m = a;
+		m += a;
+		assert_eq!(m.elements, expected.elements);
+		assert_eq!((a - a).elements, [0.0; 9]);
+		m = a;
+		m -= a;
+		assert_eq!(m.elements, [0.0; 9]);
+		expected = a;
+		expected.multiply_scalar(- 1.0);
+		assert_eq!((- a).elements, expected.elements);
+
+		m = a;
+		m[(0, 2)] = 9.0;
+		assert_eq!(m[(0, 2)], 9.0);
+		assert_eq!(m[ 6 ], 9.0);
+	}
+
+	#[test]
+	fn set_uv_transform_matches_its_steps() {
+		let mut m: Matrix3 = Matrix3::new();
+		m.set_uv_transform(0.25, 0.1, 2.0, 3.0, 0.3, 0.5, 0.5);
+
+		let mut steps: Matrix3 = Matrix3::new();
+		steps.translate(- 0.5, - 0.5);
+		steps.rotate(0.3);
+		steps.scale(2.0, 3.0);
+		steps.translate(0.5 + 0.25, 0.5 + 0.1);
+		for (a, b) in steps.elements.iter().zip(m.elements.iter()) {
+			assert!((a - b).abs() < 1e-6);
+		}
+	}
+
+	#[test]
+	fn quarter_turn_uv_transform() {
+		let mut rotated = Matrix3::new();
+		rotated.set_uv_transform(0.0, 0.0, 1.0, 1.0, ::std::f32::consts::FRAC_PI_2, 0.5, 0.5);
+
+		// uvs turn clockwise so the texture itself appears to turn counter-clockwise
+		let mut corner = Vector2 { x: 1.0, y: 0.0 };
+		corner.apply_matrix3(&rotated);
+		assert!(corner.x.abs() < 1e-6 && corner.y.abs() < 1e-6);
+	}
+}
@@ -1,30 +1,35 @@
 use super::quaternion::Quaternion;
 use super::vector3::Vector3;
 use super::euler::{Euler, RotationOrders};
+use std::ops;
+use super::float::Float;
+use super::super::errors::SingularMatrixError;
 
 #[derive(Debug, Clone, Copy)]
-pub struct Matrix4 {
-	pub elements: [f32; 16] 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Matrix4<T = f32> {
+	pub elements: [T; 16] 
 }
 
-impl Matrix4 {
-	pub fn new() -> Matrix4 {
+impl<T: Float> Matrix4<T> {
+	pub fn new() -> Matrix4<T> {
 		let elements = [
-			1.0, 0.0, 0.0, 0.0,
-			0.0, 1.0, 0.0, 0.0,
-			0.0, 0.0, 1.0, 0.0,
-			0.0, 0.0, 0.0, 1.0
+			T::one(), T::zero(), T::zero(), T::zero(),
+			T::zero(), T::one(), T::zero(), T::zero(),
+			T::zero(), T::zero(), T::one(), T::zero(),
+			T::zero(), T::zero(), T::zero(), T::one()
 		];
 		Matrix4 {
 			elements: elements
 		}
 	}
 
-	pub fn get_elements(&self) -> &[f32; 16] {
+	pub fn get_elements(&self) -> &[T; 16] {
 		&self.elements
 	}
 
-	pub fn set(&mut self, n11: f32, n12: f32, n13: f32, n14: f32, n21: f32, n22: f32, n23: f32, n24: f32, n31: f32, n32: f32, n33: f32, n34: f32, n41: f32, n42: f32, n43: f32, n44: f32) {
+	pub fn set(&mut self, n11: T, n12: T, n13: T, n14: T, n21: T, n22: T, n23: T, n24: T, n31: T, n32: T, n33: T, n34: T, n41: T, n42: T, n43: T, n44: T) {
 		self.elements[ 0 ] = n11;
 		self.elements[ 4 ] = n12;
 		self.elements[ 8 ] = n13;
@@ -45,46 +50,46 @@ impl Matrix4 {
 
 	pub fn identity(&mut self) {
 		self.set(
-			1.0f32, 0.0f32, 0.0f32, 0.0f32,
-			0.0f32, 1.0f32, 0.0f32, 0.0f32,
-			0.0f32, 0.0f32, 1.0f32, 0.0f32,
-			0.0f32, 0.0f32, 0.0f32, 1.0f32
+			T::one(), T::zero(), T::zero(), T::zero(),
+			T::zero(), T::one(), T::zero(), T::zero(),
+			T::zero(), T::zero(), T::one(), T::zero(),
+			T::zero(), T::zero(), T::zero(), T::one()
 		);
 	}
 
-	pub fn copy(&mut self, m: &Matrix4) {
+	pub fn copy(&mut self, m: &Matrix4<T>) {
 		self.elements.clone_from_slice(&m.elements);
 	}
 
-	pub fn copy_position(&mut self, m: &Matrix4) {
+	pub fn copy_position(&mut self, m: &Matrix4<T>) {
 		self.elements[ 12 ] = self.elements[ 12 ];
 		self.elements[ 13 ] = self.elements[ 13 ];
 		self.elements[ 14 ] = self.elements[ 14 ];
 	}
 
-	pub fn extract_basis(&self, x_axis: &mut Vector3, y_axis: &mut Vector3, z_axis: &mut Vector3) {
+	pub fn extract_basis(&self, x_axis: &mut Vector3<T>, y_axis: &mut Vector3<T>, z_axis: &mut Vector3<T>) {
 		x_axis.set_from_matrix_column(self, 0);
 		y_axis.set_from_matrix_column(self, 1);
 		z_axis.set_from_matrix_column(self, 2);
 	}
 
-	pub fn make_basis(&mut self, x_axis: &Vector3, y_axis: &Vector3, z_axis: &Vector3) {
+	pub fn make_basis(&mut self, x_axis: &Vector3<T>, y_axis: &Vector3<T>, z_axis: &Vector3<T>) {
 		self.set(
-			x_axis.get_x(), y_axis.get_x(), z_axis.get_x(), 0.0,
-			x_axis.get_y(), y_axis.get_y(), z_axis.get_y(), 0.0,
-			x_axis.get_z(), y_axis.get_z(), z_axis.get_z(), 0.0,
-			0.0,       0.0,       0.0,       1.0
+			x_axis.get_x(), y_axis.get_x(), z_axis.get_x(), T::zero(),
+			x_axis.get_y(), y_axis.get_y(), z_axis.get_y(), T::zero(),
+			x_axis.get_z(), y_axis.get_z(), z_axis.get_z(), T::zero(),
+			T::zero(),       T::zero(),       T::zero(),       T::one()
 		);
 	}
 
-	pub fn extract_rotation(&mut self, m: &Matrix4) {
+	pub fn extract_rotation(&mut self, m: &Matrix4<T>) {
 		let mut v1 = Vector3::new();
 		v1.set_from_matrix_column( m, 0 );
-		let scale_x = 1.0 / v1.length();
+		let scale_x = T::one() / v1.length();
 		v1.set_from_matrix_column( m, 1 );
-		let scale_y = 1.0 / v1.length();
+		let scale_y = T::one() / v1.length();
 		v1.set_from_matrix_column( m, 2 );
-		let scale_z = 1.0 / v1.length();
+		let scale_z = T::one() / v1.length();
 
 		self.elements[ 0 ] = m.elements[ 0 ] * scale_x;
 		self.elements[ 1 ] = m.elements[ 1 ] * scale_x;
@@ -99,7 +104,7 @@ impl Matrix4 {
 		self.elements[ 10] = m.elements[ 10] * scale_z;
 	}
 
-	pub fn make_rotation_from_euler(&mut self, euler: &Euler) {
+	pub fn make_rotation_from_euler(&mut self, euler: &Euler<T>) {
 		let x = euler.get_x();
 		let y = euler.get_y();
 		let z = euler.get_z();
@@ -235,18 +240,18 @@ impl Matrix4 {
 		
 
 		// last column
-		self.elements[ 3 ] = 0.0;
-		self.elements[ 7 ] = 0.0;
-		self.elements[ 11 ] = 0.0;
+		self.elements[ 3 ] = T::zero();
+		self.elements[ 7 ] = T::zero();
+		self.elements[ 11 ] = T::zero();
 
 		// bottom row
-		self.elements[ 12 ] = 0.0;
-		self.elements[ 13 ] = 0.0;
-		self.elements[ 14 ] = 0.0;
-		self.elements[ 15 ] = 1.0;
+		self.elements[ 12 ] = T::zero();
+		self.elements[ 13 ] = T::zero();
+		self.elements[ 14 ] = T::zero();
+		self.elements[ 15 ] = T::one();
 	}
 
-	pub fn make_rotation_from_quaternion(&mut self, q: &Quaternion) {
+	pub fn make_rotation_from_quaternion(&mut self, q: &Quaternion<T>) {
 		let x = q.get_x();
 		let y = q.get_y();
 		let z = q.get_z();
@@ -264,31 +269,31 @@ impl Matrix4 {
 		let wy = w * y2;
 		let wz = w * z2;
 
-		self.elements[ 0 ] = 1.0 - ( yy + zz );
+		self.elements[ 0 ] = T::one() - ( yy + zz );
 		self.elements[ 4 ] = xy - wz;
 		self.elements[ 8 ] = xz + wy;
 
 		self.elements[ 1 ] = xy + wz;
-		self.elements[ 5 ] = 1.0 - ( xx + zz );
+		self.elements[ 5 ] = T::one() - ( xx + zz );
 		self.elements[ 9 ] = yz - wx;
 
 		self.elements[ 2 ] = xz - wy;
 		self.elements[ 6 ] = yz + wx;
-		self.elements[ 10 ] = 1.0 - ( xx + yy );
+		self.elements[ 10 ] = T::one() - ( xx + yy );
 
 		// last column
-		self.elements[ 3 ] = 0.0;
-		self.elements[ 7 ] = 0.0;
-		self.elements[ 11 ] = 0.0;
+		self.elements[ 3 ] = T::zero();
+		self.elements[ 7 ] = T::zero();
+		self.elements[ 11 ] = T::zero();
 
 		// bottom row
-		self.elements[ 12 ] = 0.0;
-		self.elements[ 13 ] = 0.0;
-		self.elements[ 14 ] = 0.0;
-		self.elements[ 15 ] = 1.0;
+		self.elements[ 12 ] = T::zero();
+		self.elements[ 13 ] = T::zero();
+		self.elements[ 14 ] = T::zero();
+		self.elements[ 15 ] = T::one();
 	}
 
-	pub fn look_at(&mut self, eye: &Vector3, target: &Vector3, up: &Vector3) {
+	pub fn look_at(&mut self, eye: &Vector3<T>, target: &Vector3<T>, up: &Vector3<T>) {
 
 		let mut x = Vector3::new();
 		let mut y = Vector3::new();
@@ -297,16 +302,16 @@ impl Matrix4 {
 		z.sub_vectors( eye, target );
 		z.normalize();
 
-		if z.length_sq() == 0.0 {
-			z.set_z(1.0);
+		if z.length_sq() == T::zero() {
+			z.set_z(T::one());
 		}
 
 		x.cross_vectors( up, &z );
 		x.normalize();
 
-		if x.length_sq() == 0.0 {
+		if x.length_sq() == T::zero() {
 			let inc = {
-				z.get_z() + 0.0001
+				z.get_z() + T::from_f64(0.0001)
 			};
 			z.set_z(inc);
 			x.cross_vectors( up, &z );
@@ -327,17 +332,17 @@ impl Matrix4 {
 		self.elements[ 10 ] = z.get_z();		
 	}
 
-	pub fn multiply(&mut self, m: &Matrix4) {
+	pub fn multiply(&mut self, m: &Matrix4<T>) {
 		let s = *self;
 		self.multiply_matrices(&s, m);
 	}
 	
-	pub fn premultiply(&mut self, m: &Matrix4) {
+	pub fn premultiply(&mut self, m: &Matrix4<T>) {
 		let s = *self;
 		self.multiply_matrices(m, &s);
 	}
 
-	pub fn multiply_matrices(&mut self, a: &Matrix4, b: &Matrix4) {
+	pub fn multiply_matrices(&mut self, a: &Matrix4<T>, b: &Matrix4<T>) {
 		let a11 = a.elements[ 0 ];
 		let a12 = a.elements[ 4 ];
 		let a13 = a.elements[ 8 ];
@@ -393,7 +398,7 @@ impl Matrix4 {
 		self.elements[ 15 ] = a41 * b14 + a42 * b24 + a43 * b34 + a44 * b44;
 	}
 
-	pub fn multiply_to_array(&mut self, a: &Matrix4, b: &Matrix4, r: &mut [f32]) {
+	pub fn multiply_to_array(&mut self, a: &Matrix4<T>, b: &Matrix4<T>, r: &mut [T]) {
 		self.multiply_matrices( a, b );
 
 		r[ 0 ] = self.elements[ 0 ];
@@ -414,7 +419,7 @@ impl Matrix4 {
 		r[ 15 ] = self.elements[ 15 ];
 	}
 
-	pub fn multiply_scalar(&mut self, s: f32) {
+	pub fn multiply_scalar(&mut self, s: T) {
 		self.elements[ 0 ] *= s;
 		self.elements[ 4 ] *= s;
 		self.elements[ 8 ] *= s;
@@ -433,7 +438,7 @@ impl Matrix4 {
 		self.elements[ 15 ] *= s;
 	}
 
-	pub fn apply_to_vector3_array(&self, array: &mut [f32], offset: Option<usize>, length: Option<usize>) {
+	pub fn apply_to_vector3_array(&self, array: &mut [T], offset: Option<usize>, length: Option<usize>) {
 		let mut v1 = Vector3::new();
 		let offset: usize = match offset {
 			Some(off) => off,
@@ -454,7 +459,7 @@ impl Matrix4 {
 		}
 	}
 
-	pub fn determinant(&self) -> f32 {
+	pub fn determinant(&self) -> T {
 		let n11 = self.elements[ 0 ];
 		let n12 = self.elements[ 4 ];
 		let n13 = self.elements[ 8 ];
@@ -509,7 +514,7 @@ impl Matrix4 {
 	}
 
 	pub fn transpose(&mut self) {
-		let mut tmp: f32;
+		let mut tmp: T;
 
 		tmp = self.elements[ 1 ];
 		self.elements[ 1 ] = self.elements[ 4 ];
@@ -532,13 +537,13 @@ impl Matrix4 {
 		self.elements[ 14 ] = tmp;
 	}
 
-	pub fn set_position(&mut self, v: &Vector3) {
+	pub fn set_position(&mut self, v: &Vector3<T>) {
 		self.elements[12] = v.get_x();
 		self.elements[13] = v.get_y();
 		self.elements[14] = v.get_z();
 	}
 	
-	pub fn get_inverse(&mut self, m: &Matrix4, throw_on_degenerate: bool) {
+	pub fn get_inverse(&mut self, m: &Matrix4<T>) -> Result<(), SingularMatrixError> {
 		let n11 = m.elements[ 0 ];
 		let n21 = m.elements[ 1 ];
 		let n31 = m.elements[ 2 ];
@@ -564,26 +569,14 @@ impl Matrix4 {
 
 		let det = n11 * t11 + n21 * t12 + n31 * t13 + n41 * t14;
 
-		if det == 0.0 {
+		let det_inv = T::one() / det;
 
-			let msg = "Matrix4.getInverse(): can't invert matrix; determinant is 0";
-
-			if throw_on_degenerate == true {
-
-				panic!("{}", msg);
-
-			} else {
-
-				println!("{}", msg);
-
-			}
-
-			self.identity();
-			return;
+		if det == T::zero() || !det_inv.is_finite() {
+			return Err(SingularMatrixError {
+				determinant: det.to_f64(),
+			});
 		}
 
-		let det_inv = 1.0 / det;
-
 		self.elements[ 0 ] = t11 * det_inv;
 		self.elements[ 1 ] = ( n24 * n33 * n41 - n23 * n34 * n41 - n24 * n31 * n43 + n21 * n34 * n43 + n23 * n31 * n44 - n21 * n33 * n44 ) * det_inv;
 		self.elements[ 2 ] = ( n22 * n34 * n41 - n24 * n32 * n41 + n24 * n31 * n42 - n21 * n34 * n42 - n22 * n31 * n44 + n21 * n32 * n44 ) * det_inv;
@@ -604,9 +597,16 @@ impl Matrix4 {
 		self.elements[ 14 ] = ( n14 * n22 * n31 - n12 * n24 * n31 - n14 * n21 * n32 + n11 * n24 * n32 + n12 * n21 * n34 - n11 * n22 * n34 ) * det_inv;
 		self.elements[ 15 ] = ( n12 * n23 * n31 - n13 * n22 * n31 + n13 * n21 * n32 - n11 * n23 * n32 - n12 * n21 * n33 + n11 * n22 * n33 ) * det_inv;
 
+		Ok(())
 	}
 
-	pub fn scale(&mut self, v: &Vector3) {
+	pub fn try_inverse(&self) -> Result<Matrix4<T>, SingularMatrixError> {
+		let mut m = Matrix4::new();
+		m.get_inverse(self)?;
+		Ok(m)
+	}
+
+	pub fn scale(&mut self, v: &Vector3<T>) {
 		let x = v.get_x();
 		let y = v.get_y();
 		let z = v.get_z();
@@ -625,62 +625,62 @@ impl Matrix4 {
 		self.elements[ 11 ] *= z;
 	}
 
-	pub fn get_max_scale_on_axis(&mut self) -> f32 {
+	pub fn get_max_scale_on_axis(&self) -> T {
 		let scale_x_sq = self.elements[ 0 ] * self.elements[ 0 ] + self.elements[ 1 ] * self.elements[ 1 ] + self.elements[ 2 ] * self.elements[ 2 ];
 		let scale_y_sq = self.elements[ 4 ] * self.elements[ 4 ] + self.elements[ 5 ] * self.elements[ 5 ] + self.elements[ 6 ] * self.elements[ 6 ];
 		let scale_z_sq = self.elements[ 8 ] * self.elements[ 8 ] + self.elements[ 9 ] * self.elements[ 9 ] + self.elements[ 10 ] * self.elements[ 10 ];
 		scale_x_sq.max(scale_y_sq.max(scale_z_sq)).sqrt()
 	}
 
-	pub fn make_translation(&mut self, x: f32, y: f32, z: f32) {
+	pub fn make_translation(&mut self, x: T, y: T, z: T) {
 		self.set(
-			1.0, 0.0, 0.0, x,
-			0.0, 1.0, 0.0, y,
-			0.0, 0.0, 1.0, z,
-			0.0, 0.0, 0.0, 1.0
+			T::one(), T::zero(), T::zero(), x,
+			T::zero(), T::one(), T::zero(), y,
+			T::zero(), T::zero(), T::one(), z,
+			T::zero(), T::zero(), T::zero(), T::one()
 		);
 	}
 
-	pub fn make_rotation_x(&mut self, theta: f32) {
+	pub fn make_rotation_x(&mut self, theta: T) {
 		let c = theta.cos();
 		let s = theta.sin();
 
 		self.set(
-			1.0, 0.0,  0.0, 0.0,
-			0.0, c, -s, 0.0,
-			0.0, s,  c, 0.0,
-			0.0, 0.0,  0.0, 1.0
+			T::one(), T::zero(),  T::zero(), T::zero(),
+			T::zero(), c, -s, T::zero(),
+			T::zero(), s,  c, T::zero(),
+			T::zero(), T::zero(),  T::zero(), T::one()
 		);
 	}
 
-	pub fn make_rotation_y(&mut self, theta: f32) {
+	pub fn make_rotation_y(&mut self, theta: T) {
 		let c = theta.cos();
 		let s = theta.sin();
 
 		self.set(
-			 c, 0.0, s, 0.0,
-			 0.0, 1.0, 0.0, 0.0,
-			- s, 0.0, c, 0.0,
-			 0.0, 0.0, 0.0, 1.0
+			 c, T::zero(), s, T::zero(),
+			 T::zero(), T::one(), T::zero(), T::zero(),
+			- s, T::zero(), c, T::zero(),
+			 T::zero(), T::zero(), T::zero(), T::one()
 		);
 	}
 
-	pub fn make_rotation_z(&mut self, theta: f32) {
+	pub fn make_rotation_z(&mut self, theta: T) {
 		let c = theta.cos();
 		let s = theta.sin();
 
 		self.set(
-			c, - s, 0.0, 0.0,
-			s,  c, 0.0, 0.0,
-			0.0,  0.0, 1.0, 0.0,
-			0.0,  0.0, 0.0, 1.0
+			c, - s, T::zero(), T::zero(),
+			s,  c, T::zero(), T::zero(),
+			T::zero(),  T::zero(), T::one(), T::zero(),
+			T::zero(),  T::zero(), T::zero(), T::one()
 		);
 	}
 
-	pub fn make_rotation_axis(&mut self, axis: &Vector3, angle: f32) {
+	pub fn make_rotation_axis(&mut self, axis: &Vector3<T>, angle: T) {
 		let c = angle.cos();
 		let s = angle.sin();
-		let t = 1.0 - c;
+		let t = T::one() - c;
 		let x = axis.get_x();
 		let y = axis.get_y();
 		let z = axis.get_z();
@@ -688,29 +688,29 @@ impl Matrix4 {
 		let ty = t * y;
 
 		self.set(
-			tx * x + c, tx * y - s * z, tx * z + s * y, 0.0,
-			tx * y + s * z, ty * y + c, ty * z - s * x, 0.0,
-			tx * z - s * y, ty * z + s * x, t * z * z + c, 0.0,
-			0.0, 0.0, 0.0, 1.0
+			tx * x + c, tx * y - s * z, tx * z + s * y, T::zero(),
+			tx * y + s * z, ty * y + c, ty * z - s * x, T::zero(),
+			tx * z - s * y, ty * z + s * x, t * z * z + c, T::zero(),
+			T::zero(), T::zero(), T::zero(), T::one()
 		);
 	}
 
-	pub fn make_scale(&mut self, x: f32, y: f32, z: f32) {
+	pub fn make_scale(&mut self, x: T, y: T, z: T) {
 		self.set(
-			x, 0.0, 0.0, 0.0,
-			0.0, y, 0.0, 0.0,
-			0.0, 0.0, z, 0.0,
-			0.0, 0.0, 0.0, 1.0
+			x, T::zero(), T::zero(), T::zero(),
+			T::zero(), y, T::zero(), T::zero(),
+			T::zero(), T::zero(), z, T::zero(),
+			T::zero(), T::zero(), T::zero(), T::one()
 		);
 	}
 
-	pub fn compose(&mut self, position: &Vector3, quaternion: &Quaternion, scale: &Vector3) {
+	pub fn compose(&mut self, position: &Vector3<T>, quaternion: &Quaternion<T>, scale: &Vector3<T>) {
 		self.make_rotation_from_quaternion(quaternion);
 		self.scale(scale);
 		self.set_position(position);
 	}
 
-	pub fn decompose(&self, position: &mut Vector3, quaternion: &mut Quaternion, scale: &mut Vector3) {
+	pub fn decompose(&self, position: &mut Vector3<T>, quaternion: &mut Quaternion<T>, scale: &mut Vector3<T>) {
 
 		let mut vector = Vector3::new();
 		let mut matrix = Matrix4::new();
@@ -724,7 +724,7 @@ impl Matrix4 {
 
 		// if determine is negative, we need to invert one scale
 		let det = self.determinant();
-		if det < 0.0 {
+		if det < T::zero() {
 			sx = - sx;
 		}
 
@@ -736,9 +736,9 @@ impl Matrix4 {
 
 		(&mut matrix.elements).copy_from_slice( &self.elements ); // at this point matrix is incomplete so we can't use .copy()
 
-		let inv_sx = 1.0 / sx;
-		let inv_sy = 1.0 / sy;
-		let inv_sz = 1.0 / sz;
+		let inv_sx = T::one() / sx;
+		let inv_sy = T::one() / sy;
+		let inv_sz = T::one() / sz;
 
 		matrix.elements[ 0 ] *= inv_sx;
 		matrix.elements[ 1 ] *= inv_sx;
@@ -759,35 +759,35 @@ impl Matrix4 {
 		scale.set_z(sz);
 	}
 
-	pub fn make_frustum(&mut self, left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) {
-		let x = 2.0 * near / ( right - left );
-		let y = 2.0 * near / ( top - bottom );
+	pub fn make_frustum(&mut self, left: T, right: T, bottom: T, top: T, near: T, far: T) {
+		let x = T::two() * near / ( right - left );
+		let y = T::two() * near / ( top - bottom );
 
 		let a = ( right + left ) / ( right - left );
 		let b = ( top + bottom ) / ( top - bottom );
 		let c = - ( far + near ) / ( far - near );
-		let d = - 2.0 * far * near / ( far - near );
+		let d = - T::two() * far * near / ( far - near );
 
 		self.elements[ 0 ] = x;
-		self.elements[ 4 ] = 0.0;
+		self.elements[ 4 ] = T::zero();
 		self.elements[ 8 ] = a;
-		self.elements[ 12 ] = 0.0;
-		self.elements[ 1 ] = 0.0;
+		self.elements[ 12 ] = T::zero();
+		self.elements[ 1 ] = T::zero();
 		self.elements[ 5 ] = y;
 		self.elements[ 9 ] = b;
-		self.elements[ 13 ] = 0.0;
-		self.elements[ 2 ] = 0.0;
-		self.elements[ 6 ] = 0.0;
+		self.elements[ 13 ] = T::zero();
+		self.elements[ 2 ] = T::zero();
+		self.elements[ 6 ] = T::zero();
 		self.elements[ 10 ] = c;
 		self.elements[ 14 ] = d;
-		self.elements[ 3 ] = 0.0;
-		self.elements[ 7 ] = 0.0;
-		self.elements[ 11 ] = - 1.0;
-		self.elements[ 15 ] = 0.0;
+		self.elements[ 3 ] = T::zero();
+		self.elements[ 7 ] = T::zero();
+		self.elements[ 11 ] = - T::one();
+		self.elements[ 15 ] = T::zero();
 	}
 
-	pub fn make_perspective(&mut self, fov: f32, aspect: f32, near: f32, far: f32) {
-		let ymax = near * (fov.to_radians() * 0.5);
+	pub fn make_perspective(&mut self, fov: T, aspect: T, near: T, far: T) {
+		let ymax = near * (fov.to_radians() * T::half()).tan();
 		let ymin = - ymax;
 		let xmin = ymin * aspect;
 		let xmax = ymax * aspect;
@@ -795,34 +795,34 @@ impl Matrix4 {
 		self.make_frustum(xmin, xmax, ymin, ymax, near, far);
 	}
 
-	pub fn make_orthographic(&mut self, left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) {
-		let w = 1.0 / ( right - left );
-		let h = 1.0 / ( top - bottom );
-		let p = 1.0 / ( far - near );
+	pub fn make_orthographic(&mut self, left: T, right: T, bottom: T, top: T, near: T, far: T) {
+		let w = T::one() / ( right - left );
+		let h = T::one() / ( top - bottom );
+		let p = T::one() / ( far - near );
 
 		let x = ( right + left ) * w;
 		let y = ( top + bottom ) * h;
 		let z = ( far + near ) * p;
 
-		self.elements[ 0 ] = 2.0 * w;
-		self.elements[ 4 ] = 0.0;
-		self.elements[ 8 ] = 0.0;
+		self.elements[ 0 ] = T::two() * w;
+		self.elements[ 4 ] = T::zero();
+		self.elements[ 8 ] = T::zero();
 		self.elements[ 12 ] = - x;
-		self.elements[ 1 ] = 0.0;
-		self.elements[ 5 ] = 2.0 * h;
-		self.elements[ 9 ] = 0.0;
+		self.elements[ 1 ] = T::zero();
+		self.elements[ 5 ] = T::two() * h;
+		self.elements[ 9 ] = T::zero();
 		self.elements[ 13 ] = - y;
-		self.elements[ 2 ] = 0.0;
-		self.elements[ 6 ] = 0.0;
-		self.elements[ 10 ] = - 2.0 * p;
+		self.elements[ 2 ] = T::zero();
+		self.elements[ 6 ] = T::zero();
+		self.elements[ 10 ] = - T::two() * p;
 		self.elements[ 14 ] = - z;
-		self.elements[ 3 ] = 0.0;
-		self.elements[ 7 ] = 0.0;
-		self.elements[ 11 ] = 0.0;
-		self.elements[ 15 ] = 1.0;
+		self.elements[ 3 ] = T::zero();
+		self.elements[ 7 ] = T::zero();
+		self.elements[ 11 ] = T::zero();
+		self.elements[ 15 ] = T::one();
 	}
 
-	pub fn equals(&mut self, matrix: &Matrix4) -> bool {
+	pub fn equals(&mut self, matrix: &Matrix4<T>) -> bool {
 		let me = matrix.get_elements();
 		for i in 0..16 {
 			if self.elements[ i ] != me[ i ] {
@@ -833,7 +833,7 @@ impl Matrix4 {
 		true
 	}
 
-	pub fn copy_from_array(&mut self, array: &[f32], offset: Option<usize>) {
+	pub fn copy_from_array(&mut self, array: &[T], offset: Option<usize>) {
 		let offset = match offset {
 			Some(off) => off,
 			None => 0,
@@ -844,7 +844,7 @@ impl Matrix4 {
 		}
 	}
 
-	pub fn copy_to_array(&self, array: &mut [f32], offset: Option<usize>) {
+	pub fn copy_to_array(&self, array: &mut [T], offset: Option<usize>) {
 		let offset = match offset {
 			Some(off) => off,
 			None => 0,
@@ -869,4 +869,218 @@ impl Matrix4 {
 		array[ offset + 14 ] = self.elements[ 14 ];
 		array[ offset + 15 ] = self.elements[ 15 ];
 	}
-}
\ No newline at end of file
+
+	pub fn cast<U: Float>(&self) -> Matrix4<U> {
+		let mut m = Matrix4::new();
+		for (dst, src) in m.elements.iter_mut().zip(self.elements.iter()) {
+			*dst = src.cast();
+		}
+		m
+	}
+}
+
+impl From<Matrix4<f32>> for Matrix4<f64> {
+	fn from(m: Matrix4<f32>) -> Matrix4<f64> {
+		m.cast()
+	}
+}
+
+// addition, subtraction and negation work element by element
+impl<T: Float> ops::Add<Matrix4<T>> for Matrix4<T> {
+	type Output = Matrix4<T>;
+
+	fn add(self, rhs: Matrix4<T>) -> Matrix4<T> {
+		let mut m = self;
+		m += rhs;
+		m
+	}
+}
+
+impl<T: Float> ops::AddAssign<Matrix4<T>> for Matrix4<T> {
+	fn add_assign(&mut self, rhs: Matrix4<T>) {
+		for (e, r) in self.elements.iter_mut().zip(rhs.elements.iter()) {
+			*e += *r;
+		}
+	}
+}
+
+impl<T: Float> ops::Sub<Matrix4<T>> for Matrix4<T> {
+	type Output = Matrix4<T>;
+
+	fn sub(self, rhs: Matrix4<T>) -> Matrix4<T> {
+		let mut m = self;
+		m -= rhs;
+		m
+	}
+}
+
+impl<T: Float> ops::SubAssign<Matrix4<T>> for Matrix4<T> {
+	fn sub_assign(&mut self, rhs: Matrix4<T>) {
+		for (e, r) in self.elements.iter_mut().zip(rhs.elements.iter()) {
+			*e -= *r;
+		}
+	}
+}
+
+impl<T: Float> ops::Neg for Matrix4<T> {
+	type Output = Matrix4<T>;
+
+	fn neg(self) -> Matrix4<T> {
+		let mut m = self;
+		for e in m.elements.iter_mut() {
+			*e = - *e;
+		}
+		m
+	}
+}
+
+impl<T: Float> ops::Mul<Matrix4<T>> for Matrix4<T> {
+	type Output = Matrix4<T>;
+
+	fn mul(self, rhs: Matrix4<T>) -> Matrix4<T> {
+		let mut m = Matrix4::new();
+		m.multiply_matrices(&self, &rhs);
+		m
+	}
+}
+
+impl<T: Float> ops::MulAssign<Matrix4<T>> for Matrix4<T> {
+	fn mul_assign(&mut self, rhs: Matrix4<T>) {
+		self.multiply(&rhs);
+	}
+}
+
+impl<T: Float> ops::Mul<T> for Matrix4<T> {
+	type Output = Matrix4<T>;
+
+	fn mul(self, rhs: T) -> Matrix4<T> {
+		let mut m = self;
+		m.multiply_scalar(rhs);
+		m
+	}
+}
+
+impl<T: Float> ops::MulAssign<T> for Matrix4<T> {
+	fn mul_assign(&mut self, rhs: T) {
+		self.multiply_scalar(rhs);
+	}
+}
+
+impl<T: Float> ops::Mul<Vector3<T>> for Matrix4<T> {
+	type Output = Vector3<T>;
+
+	fn mul(self, rhs: Vector3<T>) -> Vector3<T> {
+		let mut v = rhs;
+		v.apply_matrix4(&self);
+		v
+	}
+}
+
+// elements are stored column-major, so `m[i]` indexes the flat array
+impl<T: Float> ops::Index<usize> for Matrix4<T> {
+	type Output = T;
+
+	fn index(&self, index: usize) -> &T {
+		&self.elements[ index ]
+	}
+}
+
+impl<T: Float> ops::IndexMut<usize> for Matrix4<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T {
+		&mut self.elements[ index ]
+	}
+}
+
+// `m[(row, column)]` follows the row-major order of `set`
+impl<T: Float> ops::Index<(usize, usize)> for Matrix4<T> {
+	type Output = T;
+
+	fn index(&self, index: (usize, usize)) -> &T {
+		&self.elements[ index.1 * 4 + index.0 ]
+	}
+}
+
+impl<T: Float> ops::IndexMut<(usize, usize)> for Matrix4<T> {
+	fn index_mut(&mut self, index: (usize, usize)) -> &mut T {
+		&mut self.elements[ index.1 * 4 + index.0 ]
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Matrix4;
+	use super::super::vector3::Vector3;
+	use super::super::quaternion::Quaternion;
+
+	#[test]
+	fn operators_match_methods() {
+		let mut a = Matrix4::new();
+		a.make_rotation_y(0.5);
+		let mut b = Matrix4::new();
+		b.make_translation(1.0, 2.0, 3.0);
+
+		let mut expected = Matrix4::new();
+		expected.multiply_matrices(&a, &b);
+		assert!((a * b).equals(&expected));
+		assert_eq!((a * b)[(0, 3)], expected.elements[ 12 ]);
+
+		// a + a is a * 2, a - a is all zeros and - a is a * -1
+		expected = a;
+		expected.multiply_scalar(2.0);
+		assert_eq!((a + a).elements, expected.elements);
+		let mut m = a;
+		m += a;
+		assert_eq!(m.elements, expected.elements);
+		assert_eq!((a - a).elements, [0.0; 16]);
+		m = a;
+		m -= a;
+		assert_eq!(m.elements, [0.0; 16]);
+		expected = a;
+		expected.multiply_scalar(- 1.0);
+		assert_eq!((- a).elements, expected.elements);
+
+		let v = Vector3 { x: 1.0, y: -2.0, z: 0.5 };
+		let mut applied = v;
+		applied.apply_matrix4(&b);
+		assert!((b * v).equals(&applied));
+		assert!((v + v - v * 2.0).equals(&Vector3::new()));
+		assert!((-v)[1] == 2.0);
+
+		let mut q = Quaternion::new();
+		q.set_from_axis_angle(&Vector3 { x: 0.0, y: 1.0, z: 0.0 }, 0.5);
+		let mut rotated = v;
+		rotated.apply_quaternion(&q);
+		assert!((q * v).equals(&rotated));
+	}
+
+	#[test]
+	fn double_precision_keeps_large_world_offsets() {
+		let mut m: Matrix4<f64> = Matrix4::new();
+		m.make_translation(123456.789, 0.0, 0.0);
+
+		let v = m * Vector3 { x: 0.001, y: 0.0, z: 0.0 };
+		assert!((v.x - 123456.79).abs() < 1e-9);
+
+		let single: Vector3<f32> = v.cast();
+		let double: Vector3<f64> = single.into();
+		assert!((double.x - v.x).abs() < 0.01);
+	}
+
+	#[test]
+	fn singular_matrix_inverse_is_an_error() {
+		let mut m = Matrix4::new();
+		m.make_scale(1.0, 0.0, 1.0);
+
+		let err = m.try_inverse().unwrap_err();
+		assert_eq!(err.determinant, 0.0);
+
+		let mut target = Matrix4::new();
+		target.make_translation(1.0, 2.0, 3.0);
+		assert!(target.get_inverse(&m).is_err());
+		assert_eq!(target.elements[ 12 ], 1.0);
+
+		m.make_translation(1.0, 2.0, 3.0);
+		let inverse = m.try_inverse().unwrap();
+		assert!((m * inverse).equals(&Matrix4::new()));
+	}
+}
@@ -1,3 +1,4 @@
+pub mod float;
 pub mod math_static;
 pub mod vector2;
 pub mod vector3;
@@ -5,4 +6,147 @@ pub mod quaternion;
 pub mod matrix3;
 pub mod matrix4;
 pub mod euler;
-pub mod spherical;
\ No newline at end of file
+pub mod spherical;
+pub mod box3;
+pub mod sphere;
+pub mod ray;
+pub mod plane;
+pub mod frustum;
+pub mod color;
+pub mod vector4;
+pub mod triangle;
+pub mod line3;
+pub mod box2;
+pub mod cylindrical;
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+	use serde::Serialize;
+	use serde::de::DeserializeOwned;
+	use super::vector2::Vector2;
+	use super::vector3::Vector3;
+	use super::vector4::Vector4;
+	use super::quaternion::Quaternion;
+	use super::matrix3::Matrix3;
+	use super::matrix4::Matrix4;
+	use super::euler::{Euler, RotationOrders};
+	use super::spherical::Spherical;
+	use super::cylindrical::Cylindrical;
+	use super::box2::Box2;
+	use super::box3::Box3;
+	use super::sphere::Sphere;
+	use super::ray::Ray;
+	use super::plane::Plane;
+	use super::frustum::Frustum;
+	use super::triangle::Triangle;
+	use super::line3::Line3;
+	use super::color::{Color, Hsl};
+	use super::super::core::layers::Layers;
+
+	// writes the value as JSON and with bincode, reads both back and checks that the copies
+	// write the same output again. returns the JSON for checks on the format itself
+	fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> String {
+		let json = ::serde_json::to_string(value).unwrap();
+		let back: T = ::serde_json::from_str(&json).unwrap();
+		assert_eq!(::serde_json::to_string(&back).unwrap(), json);
+
+		let bytes = ::bincode::serialize(value).unwrap();
+		let back: T = ::bincode::deserialize(&bytes).unwrap();
+		assert_eq!(::bincode::serialize(&back).unwrap(), bytes);
+
+		json
+	}
+
+	#[test]
+	fn vectors_and_rotations() {
+		assert_eq!(round_trip(&Vector2 { x: 1.5, y: - 2.0 }), r#"{"x":1.5,"y":-2.0}"#);
+		assert_eq!(round_trip(&Vector3 { x: 1.5, y: - 2.0, z: 0.25 }), r#"{"x":1.5,"y":-2.0,"z":0.25}"#);
+		round_trip(&Vector3 { x: 123456.789f64, y: 0.0, z: - 1e-9 });
+		round_trip(&Vector4 { x: 1.0, y: 2.0, z: 3.0, w: 4.0 });
+		assert_eq!(::bincode::serialize(&Vector3 { x: 1.0f32, y: 2.0, z: 3.0 }).unwrap().len(), 12);
+
+		let mut q: Quaternion = Quaternion::new();
+		q.set_from_axis_angle(&Vector3 { x: 0.0, y: 1.0, z: 0.0 }, 0.5);
+		round_trip(&q);
+
+		let mut euler = Euler::new();
+		euler.set(0.5, 0.0, - 1.0, RotationOrders::ZYX);
+		assert_eq!(round_trip(&euler), r#"{"x":0.5,"y":0.0,"z":-1.0,"order":"ZYX"}"#);
+		assert!(::serde_json::from_str::<Euler>(r#"{"x":0,"y":0,"z":0,"order":"XXY"}"#).is_err());
+
+		let mut spherical = Spherical::new();
+		spherical.set(2.0, 0.5, - 1.0);
+		assert_eq!(round_trip(&spherical), r#"{"radius":2.0,"phi":0.5,"theta":-1.0}"#);
+
+		let mut cylindrical = Cylindrical::new();
+		cylindrical.set(2.0, 0.5, - 1.0);
+		round_trip(&cylindrical);
+	}
+
+	#[test]
+	fn matrices_are_column_major_arrays() {
+		let mut m = Matrix4::new();
+		m.make_translation(1.0, 2.0, 3.0);
+		assert_eq!(round_trip(&m), "[1.0,0.0,0.0,0.0,0.0,1.0,0.0,0.0,0.0,0.0,1.0,0.0,1.0,2.0,3.0,1.0]");
+
+		let mut m3 = Matrix3::new();
+		m3.set_uv_transform(0.25, 0.0, 2.0, 2.0, 0.0, 0.5, 0.5);
+		round_trip(&m3);
+	}
+
+	#[test]
+	fn shapes() {
+		let a = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
+		let b = Vector3 { x: 1.0, y: 0.0, z: 0.0 };
+		let c = Vector3 { x: 0.0, y: 1.0, z: 0.0 };
+
+		let mut box2 = Box2::new();
+		box2.set(&Vector2 { x: 0.0, y: 0.0 }, &Vector2 { x: 1.0, y: 2.0 });
+		round_trip(&box2);
+
+		let mut box3 = Box3::new();
+		box3.set(&a, &b);
+		round_trip(&box3);
+
+		let mut sphere = Sphere::new();
+		sphere.set(&b, 2.0);
+		round_trip(&sphere);
+
+		let mut ray = Ray::new();
+		ray.set(&a, &c);
+		round_trip(&ray);
+
+		let mut plane = Plane::new();
+		plane.set(&c, - 1.0);
+		round_trip(&plane);
+
+		let mut frustum: Frustum = Frustum::new();
+		let mut projection = Matrix4::new();
+		projection.make_perspective(50.0, 1.0, 0.1, 100.0);
+		frustum.set_from_matrix(&projection);
+		round_trip(&frustum);
+
+		let mut triangle = Triangle::new();
+		triangle.set(&a, &b, &c);
+		round_trip(&triangle);
+
+		let mut line = Line3::new();
+		line.set(&a, &c);
+		round_trip(&line);
+	}
+
+	#[test]
+	fn colors_and_layers() {
+		round_trip(&Color::from_hex(0xff8800));
+
+		let mut hsl = Hsl::new();
+		Color::from_hex(0xff8800).get_hsl(&mut hsl);
+		round_trip(&hsl);
+
+		// the bare mask
+		let mut layers = Layers::new();
+		layers.enable(3);
+		assert_eq!(round_trip(&layers), "9");
+		assert_eq!(::bincode::serialize(&layers).unwrap(), 9u64.to_le_bytes());
+	}
+}
@@ -0,0 +1,195 @@
+use super::vector3::Vector3;
+use super::matrix3::Matrix3;
+use super::matrix4::Matrix4;
+use super::sphere::Sphere;
+use super::box3::Box3;
+use super::line3::Line3;
+use super::float::Float;
+use super::super::errors::SingularMatrixError;
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Plane<T = f32> {
+	pub normal: Vector3<T>,
+	pub constant: T,
+}
+
+impl<T: Float> Plane<T> {
+	pub fn new() -> Plane<T> {
+		Plane {
+			normal: Vector3 {
+				x: T::one(),
+				y: T::zero(),
+				z: T::zero(),
+			},
+			constant: T::zero(),
+		}
+	}
+
+	pub fn get_normal(&self) -> &Vector3<T> {
+		&self.normal
+	}
+
+	pub fn get_constant(&self) -> T {
+		self.constant
+	}
+
+	pub fn set(&mut self, normal: &Vector3<T>, constant: T) {
+		self.normal.copy(normal);
+		self.constant = constant;
+	}
+
+	pub fn set_components(&mut self, x: T, y: T, z: T, w: T) {
+		self.normal.set(x, y, z);
+		self.constant = w;
+	}
+
+	pub fn copy(&mut self, plane: &Plane<T>) {
+		self.normal.copy(&plane.normal);
+		self.constant = plane.constant;
+	}
+
+	pub fn distance_to_point(&self, point: &Vector3<T>) -> T {
+		self.normal.dot(point) + self.constant
+	}
+
+	pub fn set_from_normal_and_coplanar_point(&mut self, normal: &Vector3<T>, point: &Vector3<T>) {
+		self.normal.copy(normal);
+		self.constant = - point.dot(&self.normal);
+	}
+
+	pub fn set_from_coplanar_points(&mut self, a: &Vector3<T>, b: &Vector3<T>, c: &Vector3<T>) {
+		let mut v1 = Vector3::new();
+		let mut v2 = Vector3::new();
+		v1.sub_vectors(c, b);
+		v2.sub_vectors(a, b);
+		v1.cross(&v2);
+		v1.normalize();
+
+		// Q: should an error be thrown if normal is zero (e.g. degenerate plane)?
+		self.set_from_normal_and_coplanar_point(&v1, a);
+	}
+
+	pub fn normalize(&mut self) {
+		// Note: will lead to a divide by zero if the plane is invalid.
+		let inverse_normal_length = T::one() / self.normal.length();
+		self.normal.multiply_scalar(inverse_normal_length);
+		self.constant *= inverse_normal_length;
+	}
+
+	pub fn negate(&mut self) {
+		self.constant *= - T::one();
+		self.normal.negate();
+	}
+
+	pub fn distance_to_sphere(&self, sphere: &Sphere<T>) -> T {
+		self.distance_to_point(sphere.get_center()) - sphere.get_radius()
+	}
+
+	pub fn project_point(&self, point: &Vector3<T>, target: &mut Vector3<T>) {
+		target.copy(&self.normal);
+		target.multiply_scalar(- self.distance_to_point(point));
+		target.add(point);
+	}
+
+	pub fn intersect_line(&self, start: &Vector3<T>, end: &Vector3<T>) -> Option<Vector3<T>> {
+		let mut direction = Vector3::new();
+		direction.sub_vectors(end, start);
+
+		let denominator = self.normal.dot(&direction);
+
+		if denominator == T::zero() {
+			// line is coplanar, return origin
+			if self.distance_to_point(start) == T::zero() {
+				return Some(*start);
+			}
+
+			// Unsure if this is the correct method to handle this case.
+			return None;
+		}
+
+		let t = - ( start.dot(&self.normal) + self.constant ) / denominator;
+
+		if !(T::zero()..=T::one()).contains(&t) {
+			return None;
+		}
+
+		let mut target = Vector3::new();
+		target.copy(&direction);
+		target.multiply_scalar(t);
+		target.add(start);
+		Some(target)
+	}
+
+	pub fn intersects_line(&self, start: &Vector3<T>, end: &Vector3<T>) -> bool {
+		// Note: this tests if a line intersects the plane, not whether it (or its end-points) are coplanar with it.
+		let start_sign = self.distance_to_point(start);
+		let end_sign = self.distance_to_point(end);
+
+		( start_sign < T::zero() && end_sign > T::zero() ) || ( end_sign < T::zero() && start_sign > T::zero() )
+	}
+
+	pub fn intersect_line3(&self, line: &Line3<T>) -> Option<Vector3<T>> {
+		self.intersect_line(&line.start, &line.end)
+	}
+
+	pub fn intersects_line3(&self, line: &Line3<T>) -> bool {
+		self.intersects_line(&line.start, &line.end)
+	}
+
+	pub fn intersects_box(&self, b: &Box3<T>) -> bool {
+		b.intersects_plane(self)
+	}
+
+	pub fn intersects_sphere(&self, sphere: &Sphere<T>) -> bool {
+		sphere.intersects_plane(self)
+	}
+
+	pub fn coplanar_point(&self, target: &mut Vector3<T>) {
+		target.copy(&self.normal);
+		target.multiply_scalar(- self.constant);
+	}
+
+	pub fn apply_matrix4(&mut self, matrix: &Matrix4<T>, optional_normal_matrix: Option<&Matrix3<T>>) -> Result<(), SingularMatrixError> {
+		let normal_matrix = match optional_normal_matrix {
+			Some(m) => *m,
+			None => {
+				let mut m = Matrix3::new();
+				m.get_normal_matrix(matrix)?;
+				m
+			}
+		};
+
+		let mut reference_point = Vector3::new();
+		self.coplanar_point(&mut reference_point);
+		reference_point.apply_matrix4(matrix);
+
+		self.normal.apply_matrix3(&normal_matrix);
+		self.normal.normalize();
+
+		self.constant = - reference_point.dot(&self.normal);
+
+		Ok(())
+	}
+
+	pub fn translate(&mut self, offset: &Vector3<T>) {
+		self.constant -= offset.dot(&self.normal);
+	}
+
+	pub fn equals(&self, plane: &Plane<T>) -> bool {
+		plane.normal.equals(&self.normal) && ( plane.constant == self.constant )
+	}
+
+	pub fn cast<U: Float>(&self) -> Plane<U> {
+		Plane {
+			normal: self.normal.cast(),
+			constant: self.constant.cast(),
+		}
+	}
+}
+
+impl<T: Float> Default for Plane<T> {
+	fn default() -> Plane<T> {
+		Plane::new()
+	}
+}
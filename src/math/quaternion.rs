@@ -1,79 +1,82 @@
 use super::euler::{Euler, RotationOrders};
 use super::vector3::Vector3;
 use super::matrix4::Matrix4;
+use std::ops;
+use super::float::Float;
 
 #[derive(Debug, Clone, Copy)]
-pub struct Quaternion {
-	pub x: f32,
-	pub y: f32,
-	pub z: f32,
-	pub w: f32,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Quaternion<T = f32> {
+	pub x: T,
+	pub y: T,
+	pub z: T,
+	pub w: T,
 }
 
-impl Quaternion {
-	pub fn new() -> Quaternion {
+impl<T: Float> Quaternion<T> {
+	pub fn new() -> Quaternion<T> {
 		Quaternion{
-			x: 0.0f32,
-			y: 0.0f32,
-			z: 0.0f32,
-			w: 1.0f32,
+			x: T::zero(),
+			y: T::zero(),
+			z: T::zero(),
+			w: T::one(),
 		}
 	}
 
-	pub fn get_x(&self) -> f32 {
+	pub fn get_x(&self) -> T {
 		self.x
 	}
 	
-	pub fn set_x(&mut self, x: f32) {
+	pub fn set_x(&mut self, x: T) {
 		self.x = x;
 	}
 
-	pub fn get_y(&self) -> f32 {
+	pub fn get_y(&self) -> T {
 		self.y
 	}
 	
-	pub fn set_y(&mut self, y: f32) {
+	pub fn set_y(&mut self, y: T) {
 		self.y = y;
 	}
 
-	pub fn get_z(&self) -> f32 {
+	pub fn get_z(&self) -> T {
 		self.z
 	}
 	
-	pub fn set_z(&mut self, z: f32) {
+	pub fn set_z(&mut self, z: T) {
 		self.z = z;
 	}
 
-	pub fn get_w(&self) -> f32 {
+	pub fn get_w(&self) -> T {
 		self.w
 	}
 	
-	pub fn set_w(&mut self, w: f32) {
+	pub fn set_w(&mut self, w: T) {
 		self.w = w;
 	}
 	
-	pub fn set(&mut self, x: f32, y: f32, z: f32, w: f32) {
+	pub fn set(&mut self, x: T, y: T, z: T, w: T) {
 		self.x = x;
 		self.y = y;
 		self.z = z;
 		self.w = w;
 	}
 
-	pub fn copy(&mut self, quaternion: &Quaternion) {
+	pub fn copy(&mut self, quaternion: &Quaternion<T>) {
 		self.x = quaternion.x;
 		self.y = quaternion.y;
 		self.z = quaternion.z;
 		self.w = quaternion.w;
 	}
 
-	pub fn set_from_euler(&mut self, euler: &Euler) {
+	pub fn set_from_euler(&mut self, euler: &Euler<T>) {
 
-		let c1 = ( euler.get_x() / 2.0f32 ).cos();
-		let c2 = ( euler.get_y() / 2.0f32 ).cos();
-		let c3 = ( euler.get_z() / 2.0f32 ).cos();
-		let s1 = ( euler.get_x() / 2.0f32 ).sin();
-		let s2 = ( euler.get_y() / 2.0f32 ).sin();
-		let s3 = ( euler.get_z() / 2.0f32 ).sin();
+		let c1 = ( euler.get_x() / T::two() ).cos();
+		let c2 = ( euler.get_y() / T::two() ).cos();
+		let c3 = ( euler.get_z() / T::two() ).cos();
+		let s1 = ( euler.get_x() / T::two() ).sin();
+		let s2 = ( euler.get_y() / T::two() ).sin();
+		let s3 = ( euler.get_z() / T::two() ).sin();
 
 		let order = euler.get_order();
 
@@ -118,8 +121,8 @@ impl Quaternion {
 
 	}
 
-	pub fn set_from_axis_angle(&mut self, axis: &Vector3, angle: f32) {
-		let half_angle = angle / 2.0f32;
+	pub fn set_from_axis_angle(&mut self, axis: &Vector3<T>, angle: T) {
+		let half_angle = angle / T::two();
 		let s = half_angle.sin();
 
 		self.x = axis.get_x() * s;
@@ -128,7 +131,7 @@ impl Quaternion {
 		self.w = half_angle.cos();
 	}
 
-	pub fn set_from_rotation_matrix(&mut self, m: &Matrix4) {
+	pub fn set_from_rotation_matrix(&mut self, m: &Matrix4<T>) {
 		let te = m.get_elements();
 		let m11 = te[0];
 		let m12 = te[4];
@@ -140,45 +143,45 @@ impl Quaternion {
 		let m32 = te[6];
 		let m33 = te[10];
 		let trace = m11 + m22 + m33;
-		if trace > 0.0f32 {
-			let s = 0.5f32 / (trace + 1.0f32).sqrt();
-			self.w = 0.25f32 / s;
+		if trace > T::zero() {
+			let s = T::half() / (trace + T::one()).sqrt();
+			self.w = T::from_f64(0.25) / s;
 			self.x = ( m32 - m23 ) * s;
 			self.y = ( m13 - m31 ) * s;
 			self.z = ( m21 - m12 ) * s;
 		} else if m11 > m22 && m11 > m33 {
-			let s = 2.0f32 * (1.0f32 + m11 - m22 - m33).sqrt();
+			let s = T::two() * (T::one() + m11 - m22 - m33).sqrt();
 
 			self.w = ( m32 - m23 ) / s;
-			self.x = 0.25f32 * s;
+			self.x = T::from_f64(0.25) * s;
 			self.y = ( m12 + m21 ) / s;
 			self.z = ( m13 + m31 ) / s;
 		} else if m22 > m33 {
-			let s = 2.0f32 * (1.0f32 + m22 - m11 - m33);
+			let s = T::two() * (T::one() + m22 - m11 - m33).sqrt();
 			self.w = ( m13 - m31 ) / s;
 			self.x = ( m12 + m21 ) / s;
-			self.y = 0.25f32 * s;
+			self.y = T::from_f64(0.25) * s;
 			self.z = ( m23 + m32 ) / s;
 		} else {
-			let s = 2.0f32 * (1.0f32 + m33 - m11 - m22);
+			let s = T::two() * (T::one() + m33 - m11 - m22).sqrt();
 
 			self.w = ( m21 - m12 ) / s;
 			self.x = ( m13 + m31 ) / s;
 			self.y = ( m23 + m32 ) / s;
-			self.z = 0.25f32 * s;
+			self.z = T::from_f64(0.25) * s;
 		}
 	}
 
-	pub fn set_from_unit_vectors(&mut self, v_from: &Vector3, v_to: &Vector3) {
+	pub fn set_from_unit_vectors(&mut self, v_from: &Vector3<T>, v_to: &Vector3<T>) {
 		let mut v1 = Vector3::new();
-		let mut r = v_from.dot(v_to) + 1.0f32;
+		let mut r = v_from.dot(v_to) + T::one();
 
-		if r < 0.000001f32 {
-			r = 0.0f32;
+		if r < T::from_f64(0.000001) {
+			r = T::zero();
 			if v_from.get_x().abs() > v_from.get_z().abs() {
-				v1.set(- v_from.get_y(), v_from.get_x(), 0.0f32);
+				v1.set(- v_from.get_y(), v_from.get_x(), T::zero());
 			} else {
-				v1.set(0.0f32, - v_from.get_z(), v_from.get_y());
+				v1.set(T::zero(), - v_from.get_z(), v_from.get_y());
 			}
 		} else {
 			v1.cross_vectors(v_from, v_to);
@@ -197,32 +200,32 @@ impl Quaternion {
 	}
 
 	pub fn conjugate(&mut self) {
-		self.x *= - 1.0f32;
-		self.y *= - 1.0f32;
-		self.z *= - 1.0f32;
+		self.x *= - T::one();
+		self.y *= - T::one();
+		self.z *= - T::one();
 	}
 
-	pub fn dot(&self, v: &Quaternion) -> f32 {
+	pub fn dot(&self, v: &Quaternion<T>) -> T {
 		self.x * v.x + self.y * v.y + self.z * v.z + self.w * v.w
 	}
 
-	pub fn length_sq(&self) -> f32 {
+	pub fn length_sq(&self) -> T {
 		self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w
 	}
 
-	pub fn length(&self) -> f32 {
+	pub fn length(&self) -> T {
 		self.length_sq().sqrt()
 	}
 
 	pub fn normalize(&mut self) {
 		let mut l = self.length();
-		if l == 0.0f32 {
-			self.x = 0.0f32;
-			self.y = 0.0f32;
-			self.z = 0.0f32;
-			self.w = 1.0f32;
+		if l == T::zero() {
+			self.x = T::zero();
+			self.y = T::zero();
+			self.z = T::zero();
+			self.w = T::one();
 		} else {
-			l = 1.0f32 / l;
+			l = T::one() / l;
 			self.x = self.x * l;
 			self.y = self.y * l;
 			self.z = self.z * l;
@@ -230,17 +233,17 @@ impl Quaternion {
 		}
 	}
 
-	pub fn multiply(&mut self, q: &Quaternion) {
+	pub fn multiply(&mut self, q: &Quaternion<T>) {
 		let s = *self;
 		self.multiply_quaternions(&s, q);
 	}
 
-	pub fn premultiply(&mut self, q: &Quaternion) {
+	pub fn premultiply(&mut self, q: &Quaternion<T>) {
 		let s = *self;
 		self.multiply_quaternions(q, &s);
 	}
 
-	pub fn multiply_quaternions(&mut self, a: &Quaternion, b: &Quaternion) {
+	pub fn multiply_quaternions(&mut self, a: &Quaternion<T>, b: &Quaternion<T>) {
 		let qax = a.x;
 		let qay = a.y;
 		let qaz = a.z;
@@ -256,10 +259,10 @@ impl Quaternion {
 		self.w = qaw * qbw - qax * qbx - qay * qby - qaz * qbz;
 	}
 
-	pub fn slerp(&mut self, qb: &Quaternion, t: f32) {
-		if t == 0.0f32 {
+	pub fn slerp(&mut self, qb: &Quaternion<T>, t: T) {
+		if t == T::zero() {
 			return;
-		} else if t == 1.0f32 {
+		} else if t == T::one() {
 			self.copy(qb);
 			return;
 		}
@@ -271,7 +274,7 @@ impl Quaternion {
 
 		let mut cos_half_theta = w * qb.w + x * qb.x + y * qb.y + z * qb.z;
 
-		if cos_half_theta < 0.0f32 {
+		if cos_half_theta < T::zero() {
 			self.w = - qb.w;
 			self.x = - qb.x;
 			self.y = - qb.y;
@@ -282,7 +285,7 @@ impl Quaternion {
 			self.copy(qb);
 		}
 
-		if cos_half_theta >= 1.0 {
+		if cos_half_theta >= T::one() {
 			self.w = w;
 			self.x = x;
 			self.y = y;
@@ -291,19 +294,19 @@ impl Quaternion {
 			return;
 		}
 
-		let sin_half_theta = (1.0f32 - cos_half_theta * cos_half_theta).sqrt();
+		let sin_half_theta = (T::one() - cos_half_theta * cos_half_theta).sqrt();
 
-		if sin_half_theta.abs() < 0.001f32 {
-			self.w = 0.5f32 * ( w + self.w );
-			self.x = 0.5f32 * ( x + self.x );
-			self.y = 0.5f32 * ( y + self.y );
-			self.z = 0.5f32 * ( z + self.z );
+		if sin_half_theta.abs() < T::from_f64(0.001) {
+			self.w = T::half() * ( w + self.w );
+			self.x = T::half() * ( x + self.x );
+			self.y = T::half() * ( y + self.y );
+			self.z = T::half() * ( z + self.z );
 
 			return;
 		}
 
 		let half_theta = sin_half_theta.atan2(cos_half_theta);
-		let ratio_a = ((1.0f32 - t) * half_theta).sin() / sin_half_theta;
+		let ratio_a = ((T::one() - t) * half_theta).sin() / sin_half_theta;
 		let ratio_b = (t * half_theta).sin() / sin_half_theta;
 
 		self.w = w * ratio_a + self.w * ratio_b;
@@ -312,11 +315,11 @@ impl Quaternion {
 		self.z = z * ratio_a + self.z * ratio_b;
 	}
 
-	pub fn equals(&self, quaternion: &Quaternion) -> bool {
+	pub fn equals(&self, quaternion: &Quaternion<T>) -> bool {
 		( quaternion.x == self.x ) && ( quaternion.y == self.y ) && ( quaternion.z == self.z ) && ( quaternion.w == self.w )
 	}
 
-	pub fn copy_from_array(&mut self, array: &[f32], offset: Option<usize>) {
+	pub fn copy_from_array(&mut self, array: &[T], offset: Option<usize>) {
 		let offset = match offset {
 			Some(off) => off,
 			None => 0usize,
@@ -327,7 +330,7 @@ impl Quaternion {
 		self.z = array[offset + 3];
 	}
 
-	pub fn copy_to_array(&self, array: &mut [f32], offset: Option<usize>) {
+	pub fn copy_to_array(&self, array: &mut [T], offset: Option<usize>) {
 		let offset = match offset {
 			Some(off) => off,
 			None => 0usize,
@@ -338,12 +341,12 @@ impl Quaternion {
 		array[offset + 3] = self.w;
 	}
 
-	pub fn slerp_static(qa: &Quaternion, qb: &Quaternion, qm: &mut Quaternion, t: f32) {
+	pub fn slerp_static(qa: &Quaternion<T>, qb: &Quaternion<T>, qm: &mut Quaternion<T>, t: T) {
 		qm.copy(qa);
 		qm.slerp(qb, t);
 	}
 
-	pub fn slerp_flat(dst: &mut [f32], dst_offset: usize, src0: &[f32], src_offset0: usize, src1: &[f32], src_offset1: usize, t: f32) {
+	pub fn slerp_flat(dst: &mut [T], dst_offset: usize, src0: &[T], src_offset0: usize, src1: &[T], src_offset1: usize, t: T) {
 		let mut t = t;
 		let mut x0 = src0[ src_offset0 ];
 		let mut y0 = src0[ src_offset0 + 1 ];
@@ -355,12 +358,12 @@ impl Quaternion {
 		let w1 = src1[ src_offset1 + 3 ];
 
 		if w0 != w1 || x0 != x1 || y0 != y1 || z0 != z1 {
-			let mut s = 1.0f32 - t;
+			let mut s = T::one() - t;
 			let cos = x0 * x1 + y0 * y1 + z0 * z1 + w0 * w1;
-			let dir = if cos >= 0.0f32 { 1.0f32 } else { -1.0f32 };
-			let sqr_sin = 1.0f32 - cos * cos;
+			let dir = if cos >= T::zero() { T::one() } else { -T::one() };
+			let sqr_sin = T::one() - cos * cos;
 
-			if sqr_sin > ::std::f32::EPSILON {
+			if sqr_sin > T::epsilon() {
 				let sin = sqr_sin.sqrt();
 				let len = sin.atan2(cos * dir);
 				s = (s * len).sin() / sin;
@@ -373,8 +376,8 @@ impl Quaternion {
 			z0 = z0 * s + z1 * t_dir;
 			w0 = w0 * s + w1 * t_dir;
 
-			if s == 1.0f32 - t {
-				let f = 1.0f32 / ( x0 * x0 + y0 * y0 + z0 * z0 + w0 * w0 ).sqrt();
+			if s == T::one() - t {
+				let f = T::one() / ( x0 * x0 + y0 * y0 + z0 * z0 + w0 * w0 ).sqrt();
 				x0 *= f;
 				y0 *= f;
 				z0 *= f;
@@ -387,4 +390,170 @@ impl Quaternion {
 		dst[ dst_offset + 2 ] = z0;
 		dst[ dst_offset + 3 ] = w0;
 	}
-}
\ No newline at end of file
+
+	pub fn cast<U: Float>(&self) -> Quaternion<U> {
+		Quaternion {
+			x: self.x.cast(),
+			y: self.y.cast(),
+			z: self.z.cast(),
+			w: self.w.cast(),
+		}
+	}
+}
+
+impl From<Quaternion<f32>> for Quaternion<f64> {
+	fn from(q: Quaternion<f32>) -> Quaternion<f64> {
+		q.cast()
+	}
+}
+
+// component-wise, as for blending or averaging quaternions before normalizing
+impl<T: Float> ops::Add<Quaternion<T>> for Quaternion<T> {
+	type Output = Quaternion<T>;
+
+	fn add(self, rhs: Quaternion<T>) -> Quaternion<T> {
+		Quaternion {
+			x: self.x + rhs.x,
+			y: self.y + rhs.y,
+			z: self.z + rhs.z,
+			w: self.w + rhs.w,
+		}
+	}
+}
+
+impl<T: Float> ops::AddAssign<Quaternion<T>> for Quaternion<T> {
+	fn add_assign(&mut self, rhs: Quaternion<T>) {
+		*self = *self + rhs;
+	}
+}
+
+impl<T: Float> ops::Sub<Quaternion<T>> for Quaternion<T> {
+	type Output = Quaternion<T>;
+
+	fn sub(self, rhs: Quaternion<T>) -> Quaternion<T> {
+		Quaternion {
+			x: self.x - rhs.x,
+			y: self.y - rhs.y,
+			z: self.z - rhs.z,
+			w: self.w - rhs.w,
+		}
+	}
+}
+
+impl<T: Float> ops::SubAssign<Quaternion<T>> for Quaternion<T> {
+	fn sub_assign(&mut self, rhs: Quaternion<T>) {
+		*self = *self - rhs;
+	}
+}
+
+impl<T: Float> ops::Mul<Quaternion<T>> for Quaternion<T> {
+	type Output = Quaternion<T>;
+
+	fn mul(self, rhs: Quaternion<T>) -> Quaternion<T> {
+		let mut q = Quaternion::new();
+		q.multiply_quaternions(&self, &rhs);
+		q
+	}
+}
+
+impl<T: Float> ops::MulAssign<Quaternion<T>> for Quaternion<T> {
+	fn mul_assign(&mut self, rhs: Quaternion<T>) {
+		self.multiply(&rhs);
+	}
+}
+
+impl<T: Float> ops::Mul<Vector3<T>> for Quaternion<T> {
+	type Output = Vector3<T>;
+
+	fn mul(self, rhs: Vector3<T>) -> Vector3<T> {
+		let mut v = rhs;
+		v.apply_quaternion(&self);
+		v
+	}
+}
+
+impl<T: Float> ops::Neg for Quaternion<T> {
+	type Output = Quaternion<T>;
+
+	fn neg(self) -> Quaternion<T> {
+		Quaternion {
+			x: - self.x,
+			y: - self.y,
+			z: - self.z,
+			w: - self.w,
+		}
+	}
+}
+
+impl<T: Float> ops::Index<usize> for Quaternion<T> {
+	type Output = T;
+
+	fn index(&self, index: usize) -> &T {
+		match index {
+			0 => &self.x,
+			1 => &self.y,
+			2 => &self.z,
+			3 => &self.w,
+			_ => panic!("index out of range: {:?}", index)
+		}
+	}
+}
+
+impl<T: Float> ops::IndexMut<usize> for Quaternion<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T {
+		match index {
+			0 => &mut self.x,
+			1 => &mut self.y,
+			2 => &mut self.z,
+			3 => &mut self.w,
+			_ => panic!("index out of range: {:?}", index)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Quaternion;
+	use super::super::vector3::Vector3;
+
+	#[test]
+	fn operators_match_methods() {
+		let mut a: Quaternion = Quaternion::new();
+		a.set_from_axis_angle(&Vector3 { x: 0.0, y: 1.0, z: 0.0 }, 0.5);
+		let mut b = Quaternion::new();
+		b.set_from_axis_angle(&Vector3 { x: 1.0, y: 0.0, z: 0.0 }, - 1.25);
+
+		let mut expected = Quaternion::new();
+		expected.multiply_quaternions(&a, &b);
+		assert!((a * b).equals(&expected));
+		let mut q = a;
+		q *= b;
+		assert!(q.equals(&expected));
+
+		let v = Vector3 { x: 1.0, y: - 2.0, z: 0.5 };
+		let mut rotated = v;
+		rotated.apply_quaternion(&a);
+		assert!((a * v).equals(&rotated));
+
+		expected.set(a.x + b.x, a.y + b.y, a.z + b.z, a.w + b.w);
+		assert!((a + b).equals(&expected));
+		q = a;
+		q += b;
+		assert!(q.equals(&expected));
+
+		expected.set(a.x - b.x, a.y - b.y, a.z - b.z, a.w - b.w);
+		assert!((a - b).equals(&expected));
+		q = a;
+		q -= b;
+		assert!(q.equals(&expected));
+
+		// the same rotation, so it rotates vectors the same way
+		assert!((- a).equals(&Quaternion { x: - a.x, y: - a.y, z: - a.z, w: - a.w }));
+		let back = - a * v;
+		assert!((back.x - rotated.x).abs() < 1e-6 && (back.y - rotated.y).abs() < 1e-6 && (back.z - rotated.z).abs() < 1e-6);
+
+		q = a;
+		q[3] = 0.0;
+		assert_eq!((q[0], q[1], q[2], q[3]), (a.x, a.y, a.z, 0.0));
+	}
+}
@@ -0,0 +1,483 @@
+use super::vector3::Vector3;
+use super::matrix4::Matrix4;
+use super::sphere::Sphere;
+use super::plane::Plane;
+use super::box3::Box3;
+use super::float::Float;
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ray<T = f32> {
+	pub origin: Vector3<T>,
+	pub direction: Vector3<T>,
+}
+
+impl<T: Float> Ray<T> {
+	pub fn new() -> Ray<T> {
+		Ray {
+			origin: Vector3::new(),
+			direction: Vector3 {
+				x: T::zero(),
+				y: T::zero(),
+				z: - T::one(),
+			},
+		}
+	}
+
+	pub fn get_origin(&self) -> &Vector3<T> {
+		&self.origin
+	}
+
+	pub fn get_direction(&self) -> &Vector3<T> {
+		&self.direction
+	}
+
+	pub fn set(&mut self, origin: &Vector3<T>, direction: &Vector3<T>) {
+		self.origin.copy(origin);
+		self.direction.copy(direction);
+	}
+
+	pub fn copy(&mut self, ray: &Ray<T>) {
+		self.origin.copy(&ray.origin);
+		self.direction.copy(&ray.direction);
+	}
+
+	pub fn at(&self, t: T, target: &mut Vector3<T>) {
+		target.copy(&self.direction);
+		target.multiply_scalar(t);
+		target.add(&self.origin);
+	}
+
+	pub fn look_at(&mut self, v: &Vector3<T>) {
+		self.direction.sub_vectors(v, &self.origin);
+		self.direction.normalize();
+	}
+
+	pub fn recast(&mut self, t: T) {
+		let mut v1 = Vector3::new();
+		self.at(t, &mut v1);
+		self.origin.copy(&v1);
+	}
+
+	pub fn closest_point_to_point(&self, point: &Vector3<T>, target: &mut Vector3<T>) {
+		target.sub_vectors(point, &self.origin);
+		let direction_distance = target.dot(&self.direction);
+
+		if direction_distance < T::zero() {
+			target.copy(&self.origin);
+			return;
+		}
+
+		self.at(direction_distance, target);
+	}
+
+	pub fn distance_to_point(&self, point: &Vector3<T>) -> T {
+		self.distance_sq_to_point(point).sqrt()
+	}
+
+	pub fn distance_sq_to_point(&self, point: &Vector3<T>) -> T {
+		let mut v1 = Vector3::new();
+		v1.sub_vectors(point, &self.origin);
+		let direction_distance = v1.dot(&self.direction);
+
+		// point behind the ray
+		if direction_distance < T::zero() {
+			return self.origin.distance_to_squared(point);
+		}
+
+		self.at(direction_distance, &mut v1);
+		v1.distance_to_squared(point)
+	}
+
+	pub fn distance_sq_to_segment(&self, v0: &Vector3<T>, v1: &Vector3<T>, optional_point_on_ray: Option<&mut Vector3<T>>, optional_point_on_segment: Option<&mut Vector3<T>>) -> T {
+		// from http://www.geometrictools.com/GTEngine/Include/Mathematics/GteDistRaySegment.h
+		// It returns the min distance between the ray and the segment
+		// defined by v0 and v1
+		// It can also set two optional targets :
+		// - The closest point on the ray
+		// - The closest point on the segment
+
+		let mut seg_center = Vector3::new();
+		let mut seg_dir = Vector3::new();
+		let mut diff = Vector3::new();
+
+		seg_center.add_vectors(v0, v1);
+		seg_center.multiply_scalar(T::half());
+		seg_dir.sub_vectors(v1, v0);
+		seg_dir.normalize();
+		diff.sub_vectors(&self.origin, &seg_center);
+
+		let seg_extent = v0.distance_to(v1) * T::half();
+		let a01 = - self.direction.dot(&seg_dir);
+		let b0 = diff.dot(&self.direction);
+		let b1 = - diff.dot(&seg_dir);
+		let c = diff.length_sq();
+		let det = ( T::one() - a01 * a01 ).abs();
+		let mut s0;
+		let mut s1;
+		let sqr_dist;
+		let ext_det;
+
+		if det > T::zero() {
+			// The ray and segment are not parallel.
+
+			s0 = a01 * b1 - b0;
+			s1 = a01 * b0 - b1;
+			ext_det = seg_extent * det;
+
+			if s0 >= T::zero() {
+				if s1 >= - ext_det {
+					if s1 <= ext_det {
+						// region 0
+						// Minimum at interior points of ray and segment.
+
+						let inv_det = T::one() / det;
+						s0 *= inv_det;
+						s1 *= inv_det;
+						sqr_dist = s0 * ( s0 + a01 * s1 + T::two() * b0 ) + s1 * ( a01 * s0 + s1 + T::two() * b1 ) + c;
+					} else {
+						// region 1
+
+						s1 = seg_extent;
+						s0 = ( - ( a01 * s1 + b0 ) ).max(T::zero());
+						sqr_dist = - s0 * s0 + s1 * ( s1 + T::two() * b1 ) + c;
+					}
+				} else {
+					// region 5
+
+					s1 = - seg_extent;
+					s0 = ( - ( a01 * s1 + b0 ) ).max(T::zero());
+					sqr_dist = - s0 * s0 + s1 * ( s1 + T::two() * b1 ) + c;
+				}
+			} else {
+				if s1 <= - ext_det {
+					// region 4
+
+					s0 = ( - ( - a01 * seg_extent + b0 ) ).max(T::zero());
+					s1 = if s0 > T::zero() { - seg_extent } else { ( - seg_extent ).max(( - b1 ).min(seg_extent)) };
+					sqr_dist = - s0 * s0 + s1 * ( s1 + T::two() * b1 ) + c;
+				} else if s1 <= ext_det {
+					// region 3
+
+					s0 = T::zero();
+					s1 = ( - seg_extent ).max(( - b1 ).min(seg_extent));
+					sqr_dist = s1 * ( s1 + T::two() * b1 ) + c;
+				} else {
+					// region 2
+
+					s0 = ( - ( a01 * seg_extent + b0 ) ).max(T::zero());
+					s1 = if s0 > T::zero() { seg_extent } else { ( - seg_extent ).max(( - b1 ).min(seg_extent)) };
+					sqr_dist = - s0 * s0 + s1 * ( s1 + T::two() * b1 ) + c;
+				}
+			}
+		} else {
+			// Ray and segment are parallel.
+
+			s1 = if a01 > T::zero() { - seg_extent } else { seg_extent };
+			s0 = ( - ( a01 * s1 + b0 ) ).max(T::zero());
+			sqr_dist = - s0 * s0 + s1 * ( s1 + T::two() * b1 ) + c;
+		}
+
+		if let Some(point_on_ray) = optional_point_on_ray {
+			self.at(s0, point_on_ray);
+		}
+
+		if let Some(point_on_segment) = optional_point_on_segment {
+			point_on_segment.copy(&seg_dir);
+			point_on_segment.multiply_scalar(s1);
+			point_on_segment.add(&seg_center);
+		}
+
+		sqr_dist
+	}
+
+	pub fn intersect_sphere(&self, sphere: &Sphere<T>) -> Option<Vector3<T>> {
+		let mut v1 = Vector3::new();
+		v1.sub_vectors(sphere.get_center(), &self.origin);
+		let tca = v1.dot(&self.direction);
+		let d2 = v1.dot(&v1) - tca * tca;
+		let radius2 = sphere.get_radius() * sphere.get_radius();
+
+		if d2 > radius2 {
+			return None;
+		}
+
+		let thc = ( radius2 - d2 ).sqrt();
+
+		// t0 = first intersect point - entrance on front of sphere
+		let t0 = tca - thc;
+
+		// t1 = second intersect point - exit point on back of sphere
+		let t1 = tca + thc;
+
+		// test to see if both t0 and t1 are behind the ray - if so, return None
+		if t0 < T::zero() && t1 < T::zero() {
+			return None;
+		}
+
+		// test to see if t0 is behind the ray:
+		// if it is, the ray is inside the sphere, so return the second exit point scaled by t1,
+		// in order to always return an intersect point that is in front of the ray.
+		let t = if t0 < T::zero() { t1 } else { t0 };
+
+		let mut target = Vector3::new();
+		self.at(t, &mut target);
+		Some(target)
+	}
+
+	pub fn intersects_sphere(&self, sphere: &Sphere<T>) -> bool {
+		self.distance_to_point(sphere.get_center()) <= sphere.get_radius()
+	}
+
+	pub fn distance_to_plane(&self, plane: &Plane<T>) -> Option<T> {
+		let denominator = plane.get_normal().dot(&self.direction);
+
+		if denominator == T::zero() {
+			// line is coplanar, return origin
+			if plane.distance_to_point(&self.origin) == T::zero() {
+				return Some(T::zero());
+			}
+
+			return None;
+		}
+
+		let t = - ( self.origin.dot(plane.get_normal()) + plane.get_constant() ) / denominator;
+
+		// Return if the ray never intersects the plane
+		if t >= T::zero() { Some(t) } else { None }
+	}
+
+	pub fn intersect_plane(&self, plane: &Plane<T>) -> Option<Vector3<T>> {
+		self.distance_to_plane(plane).map(|t| {
+			let mut target = Vector3::new();
+			self.at(t, &mut target);
+			target
+		})
+	}
+
+	pub fn intersects_plane(&self, plane: &Plane<T>) -> bool {
+		// check if the ray lies on the plane first
+		let dist_to_point = plane.distance_to_point(&self.origin);
+
+		if dist_to_point == T::zero() {
+			return true;
+		}
+
+		let denominator = plane.get_normal().dot(&self.direction);
+
+		// ray origin is behind the plane (and is pointing behind it)
+		denominator * dist_to_point < T::zero()
+	}
+
+	pub fn intersect_box(&self, b: &Box3<T>) -> Option<Vector3<T>> {
+		let mut tmin;
+		let mut tmax;
+		let tymin;
+		let tymax;
+		let tzmin;
+		let tzmax;
+
+		let invdirx = T::one() / self.direction.x;
+		let invdiry = T::one() / self.direction.y;
+		let invdirz = T::one() / self.direction.z;
+
+		let origin = &self.origin;
+
+		if invdirx >= T::zero() {
+			tmin = ( b.min.x - origin.x ) * invdirx;
+			tmax = ( b.max.x - origin.x ) * invdirx;
+		} else {
+			tmin = ( b.max.x - origin.x ) * invdirx;
+			tmax = ( b.min.x - origin.x ) * invdirx;
+		}
+
+		if invdiry >= T::zero() {
+			tymin = ( b.min.y - origin.y ) * invdiry;
+			tymax = ( b.max.y - origin.y ) * invdiry;
+		} else {
+			tymin = ( b.max.y - origin.y ) * invdiry;
+			tymax = ( b.min.y - origin.y ) * invdiry;
+		}
+
+		if ( tmin > tymax ) || ( tymin > tmax ) {
+			return None;
+		}
+
+		// These lines also handle the case where tmin or tmax is NaN
+		// (result of 0 * Infinity).
+
+		if tymin > tmin || tmin.is_nan() {
+			tmin = tymin;
+		}
+
+		if tymax < tmax || tmax.is_nan() {
+			tmax = tymax;
+		}
+
+		if invdirz >= T::zero() {
+			tzmin = ( b.min.z - origin.z ) * invdirz;
+			tzmax = ( b.max.z - origin.z ) * invdirz;
+		} else {
+			tzmin = ( b.max.z - origin.z ) * invdirz;
+			tzmax = ( b.min.z - origin.z ) * invdirz;
+		}
+
+		if ( tmin > tzmax ) || ( tzmin > tmax ) {
+			return None;
+		}
+
+		if tzmin > tmin || tmin.is_nan() {
+			tmin = tzmin;
+		}
+
+		if tzmax < tmax || tmax.is_nan() {
+			tmax = tzmax;
+		}
+
+		//return point closest to the ray (positive side)
+
+		if tmax < T::zero() {
+			return None;
+		}
+
+		let mut target = Vector3::new();
+		self.at(if tmin >= T::zero() { tmin } else { tmax }, &mut target);
+		Some(target)
+	}
+
+	pub fn intersects_box(&self, b: &Box3<T>) -> bool {
+		self.intersect_box(b).is_some()
+	}
+
+	pub fn intersect_triangle(&self, a: &Vector3<T>, b: &Vector3<T>, c: &Vector3<T>, backface_culling: bool) -> Option<Vector3<T>> {
+		// from http://www.geometrictools.com/GTEngine/Include/Mathematics/GteIntrRay3Triangle3.h
+
+		let mut diff = Vector3::new();
+		let mut edge1 = Vector3::new();
+		let mut edge2 = Vector3::new();
+		let mut normal = Vector3::new();
+
+		edge1.sub_vectors(b, a);
+		edge2.sub_vectors(c, a);
+		normal.cross_vectors(&edge1, &edge2);
+
+		// Solve Q + t*D = b1*E1 + b2*E2 (Q = kDiff, D = ray direction,
+		// E1 = kEdge1, E2 = kEdge2, N = Cross(E1,E2)) by
+		//   |Dot(D,N)|*b1 = sign(Dot(D,N))*Dot(D,Cross(Q,E2))
+		//   |Dot(D,N)|*b2 = sign(Dot(D,N))*Dot(D,Cross(E1,Q))
+		//   |Dot(D,N)|*t = -sign(Dot(D,N))*Dot(Q,N)
+		let mut d_dot_n = self.direction.dot(&normal);
+		let sign;
+
+		if d_dot_n > T::zero() {
+			if backface_culling {
+				return None;
+			}
+			sign = T::one();
+		} else if d_dot_n < T::zero() {
+			sign = - T::one();
+			d_dot_n = - d_dot_n;
+		} else {
+			return None;
+		}
+
+		let mut diff_x_edge2 = Vector3::new();
+		diff.sub_vectors(&self.origin, a);
+		diff_x_edge2.cross_vectors(&diff, &edge2);
+		let d_dot_q_x_e2 = sign * self.direction.dot(&diff_x_edge2);
+
+		// b1 < 0, no intersection
+		if d_dot_q_x_e2 < T::zero() {
+			return None;
+		}
+
+		edge1.cross(&diff);
+		let d_dot_e1_x_q = sign * self.direction.dot(&edge1);
+
+		// b2 < 0, no intersection
+		if d_dot_e1_x_q < T::zero() {
+			return None;
+		}
+
+		// b1+b2 > 1, no intersection
+		if d_dot_q_x_e2 + d_dot_e1_x_q > d_dot_n {
+			return None;
+		}
+
+		// Line intersects triangle, check if ray does.
+		let q_dot_n = - sign * diff.dot(&normal);
+
+		// t < 0, no intersection
+		if q_dot_n < T::zero() {
+			return None;
+		}
+
+		// Ray intersects triangle.
+		let mut target = Vector3::new();
+		self.at(q_dot_n / d_dot_n, &mut target);
+		Some(target)
+	}
+
+	pub fn apply_matrix4(&mut self, matrix4: &Matrix4<T>) {
+		self.direction.add(&self.origin);
+		self.direction.apply_matrix4(matrix4);
+		self.origin.apply_matrix4(matrix4);
+		self.direction.sub(&self.origin);
+		self.direction.normalize();
+	}
+
+	pub fn equals(&self, ray: &Ray<T>) -> bool {
+		ray.origin.equals(&self.origin) && ray.direction.equals(&self.direction)
+	}
+
+	pub fn cast<U: Float>(&self) -> Ray<U> {
+		Ray {
+			origin: self.origin.cast(),
+			direction: self.direction.cast(),
+		}
+	}
+}
+
+impl<T: Float> Default for Ray<T> {
+	fn default() -> Ray<T> {
+		Ray::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Ray;
+	use super::super::vector3::Vector3;
+	use super::super::box3::Box3;
+
+	#[test]
+	fn intersect_triangle_honours_backface_culling() {
+		// a new ray looks down -z, like a default camera
+		let mut ray = Ray::new();
+		ray.origin.set(0.25, 0.25, 5.0);
+		assert!(ray.direction.equals(&Vector3 { x: 0.0, y: 0.0, z: -1.0 }));
+
+		let a = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
+		let b = Vector3 { x: 1.0, y: 0.0, z: 0.0 };
+		let c = Vector3 { x: 0.0, y: 1.0, z: 0.0 };
+
+		let hit = ray.intersect_triangle(&a, &b, &c, true).unwrap();
+		assert!(hit.equals(&Vector3 { x: 0.25, y: 0.25, z: 0.0 }));
+
+		assert!(ray.intersect_triangle(&a, &c, &b, true).is_none());
+		assert!(ray.intersect_triangle(&a, &c, &b, false).is_some());
+	}
+
+	#[test]
+	fn intersect_box_returns_entry_point() {
+		let mut b = Box3::new();
+		b.set(&Vector3 { x: -1.0, y: -1.0, z: -1.0 }, &Vector3 { x: 1.0, y: 1.0, z: 1.0 });
+
+		let mut ray = Ray::new();
+		ray.set(&Vector3 { x: -5.0, y: 0.0, z: 0.0 }, &Vector3 { x: 1.0, y: 0.0, z: 0.0 });
+		assert!(ray.intersect_box(&b).unwrap().equals(&Vector3 { x: -1.0, y: 0.0, z: 0.0 }));
+
+		ray.look_at(&Vector3 { x: -5.0, y: 5.0, z: 0.0 });
+		assert!(!ray.intersects_box(&b));
+	}
+}
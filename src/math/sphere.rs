@@ -0,0 +1,186 @@
+use super::vector3::Vector3;
+use super::matrix4::Matrix4;
+use super::box3::Box3;
+use super::plane::Plane;
+use super::float::Float;
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sphere<T = f32> {
+	pub center: Vector3<T>,
+	pub radius: T,
+}
+
+impl<T: Float> Sphere<T> {
+	pub fn new() -> Sphere<T> {
+		Sphere {
+			center: Vector3::new(),
+			radius: T::zero(),
+		}
+	}
+
+	pub fn get_center(&self) -> &Vector3<T> {
+		&self.center
+	}
+
+	pub fn get_radius(&self) -> T {
+		self.radius
+	}
+
+	pub fn set(&mut self, center: &Vector3<T>, radius: T) {
+		self.center.copy(center);
+		self.radius = radius;
+	}
+
+	pub fn copy(&mut self, sphere: &Sphere<T>) {
+		self.center.copy(&sphere.center);
+		self.radius = sphere.radius;
+	}
+
+	pub fn set_from_points(&mut self, points: &[Vector3<T>], optional_center: Option<&Vector3<T>>) {
+		match optional_center {
+			Some(center) => self.center.copy(center),
+			None => {
+				let mut b = Box3::new();
+				b.set_from_points(points);
+				b.get_center(&mut self.center);
+			}
+		}
+
+		let mut max_radius_sq = T::zero();
+		for point in points {
+			max_radius_sq = max_radius_sq.max(self.center.distance_to_squared(point));
+		}
+
+		self.radius = max_radius_sq.sqrt();
+	}
+
+	pub fn empty(&self) -> bool {
+		self.radius <= T::zero()
+	}
+
+	pub fn contains_point(&self, point: &Vector3<T>) -> bool {
+		point.distance_to_squared(&self.center) <= ( self.radius * self.radius )
+	}
+
+	pub fn distance_to_point(&self, point: &Vector3<T>) -> T {
+		point.distance_to(&self.center) - self.radius
+	}
+
+	pub fn intersects_sphere(&self, sphere: &Sphere<T>) -> bool {
+		let radius_sum = self.radius + sphere.radius;
+		sphere.center.distance_to_squared(&self.center) <= ( radius_sum * radius_sum )
+	}
+
+	pub fn intersects_box(&self, b: &Box3<T>) -> bool {
+		b.intersects_sphere(self)
+	}
+
+	pub fn intersects_plane(&self, plane: &Plane<T>) -> bool {
+		plane.distance_to_point(&self.center).abs() <= self.radius
+	}
+
+	pub fn clamp_point(&self, point: &Vector3<T>, target: &mut Vector3<T>) {
+		let delta_length_sq = self.center.distance_to_squared(point);
+
+		target.copy(point);
+
+		if delta_length_sq > ( self.radius * self.radius ) {
+			target.sub(&self.center);
+			target.normalize();
+			target.multiply_scalar(self.radius);
+			target.add(&self.center);
+		}
+	}
+
+	pub fn get_bounding_box(&self, target: &mut Box3<T>) {
+		target.set(&self.center, &self.center);
+		target.expand_by_scalar(self.radius);
+	}
+
+	pub fn apply_matrix4(&mut self, matrix: &Matrix4<T>) {
+		self.center.apply_matrix4(matrix);
+		self.radius *= matrix.get_max_scale_on_axis();
+	}
+
+	pub fn translate(&mut self, offset: &Vector3<T>) {
+		self.center.add(offset);
+	}
+
+	pub fn equals(&self, sphere: &Sphere<T>) -> bool {
+		sphere.center.equals(&self.center) && ( sphere.radius == self.radius )
+	}
+
+	pub fn cast<U: Float>(&self) -> Sphere<U> {
+		Sphere {
+			center: self.center.cast(),
+			radius: self.radius.cast(),
+		}
+	}
+}
+
+impl<T: Float> Default for Sphere<T> {
+	fn default() -> Sphere<T> {
+		Sphere::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Sphere;
+	use super::super::vector3::Vector3;
+	use super::super::matrix4::Matrix4;
+	use super::super::box3::Box3;
+
+	#[test]
+	fn set_from_points_and_queries() {
+		let points = [
+			Vector3 { x: - 1.0, y: 0.0, z: 0.0 },
+			Vector3 { x: 3.0, y: 0.0, z: 0.0 },
+			Vector3 { x: 1.0, y: 1.0, z: 0.0 },
+		];
+
+		// without a center the box center is used
+		let mut s = Sphere::new();
+		s.set_from_points(&points, None);
+		assert!(s.center.equals(&Vector3 { x: 1.0, y: 0.5, z: 0.0 }));
+		assert!((s.radius - 4.25f32.sqrt()).abs() < 1e-6);
+
+		s.set_from_points(&points, Some(&Vector3 { x: 0.0, y: 0.0, z: 0.0 }));
+		assert!(s.center.equals(&Vector3 { x: 0.0, y: 0.0, z: 0.0 }));
+		assert_eq!(s.radius, 3.0);
+
+		assert!(s.contains_point(&Vector3 { x: 0.0, y: 3.0, z: 0.0 }));
+		assert!(!s.contains_point(&Vector3 { x: 0.0, y: 3.1, z: 0.0 }));
+
+		let mut target = Vector3::new();
+		s.clamp_point(&Vector3 { x: 0.0, y: 0.0, z: 6.0 }, &mut target);
+		assert!(target.equals(&Vector3 { x: 0.0, y: 0.0, z: 3.0 }));
+		s.clamp_point(&Vector3 { x: 1.0, y: 1.0, z: 1.0 }, &mut target);
+		assert!(target.equals(&Vector3 { x: 1.0, y: 1.0, z: 1.0 }));
+
+		let mut b = Box3::new();
+		b.set(&Vector3 { x: 2.5, y: 2.5, z: - 1.0 }, &Vector3 { x: 4.0, y: 4.0, z: 1.0 });
+		assert!(!b.intersects_sphere(&s));
+		b.min.set(2.0, 2.0, - 1.0);
+		assert!(b.intersects_sphere(&s));
+	}
+
+	#[test]
+	fn apply_matrix4_scales_the_radius() {
+		let mut s = Sphere::new();
+		s.set(&Vector3 { x: 1.0, y: 0.0, z: 0.0 }, 2.0);
+
+		let mut m = Matrix4::new();
+		m.make_scale(3.0, 3.0, 3.0);
+		s.apply_matrix4(&m);
+		assert!(s.center.equals(&Vector3 { x: 3.0, y: 0.0, z: 0.0 }));
+		assert_eq!(s.radius, 6.0);
+
+		// a non-uniform scale grows the radius by the largest axis
+		m.make_scale(1.0, 4.0, 0.5);
+		assert_eq!(m.get_max_scale_on_axis(), 4.0);
+		s.apply_matrix4(&m);
+		assert_eq!(s.radius, 24.0);
+	}
+}
@@ -1,72 +1,82 @@
 use super::vector3::Vector3;
 use super::math_static::clamp;
+use super::float::Float;
 
 #[derive(Debug, Clone, Copy)]
-pub struct Spherical {
-	pub radius: f32,
-	pub phi: f32,
-	pub theta: f32,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Spherical<T = f32> {
+	pub radius: T,
+	pub phi: T,
+	pub theta: T,
 }
 
-impl Spherical {
-	pub fn new() -> Spherical {
+impl<T: Float> Spherical<T> {
+	pub fn new() -> Spherical<T> {
 		Spherical {
-			radius: 1.0,
-			phi: 0.0,
-			theta: 0.0,
+			radius: T::one(),
+			phi: T::zero(),
+			theta: T::zero(),
 		}
 	}
 
-	pub fn get_radius(&self) -> f32 {
+	pub fn get_radius(&self) -> T {
 		self.radius
 	}
 
-	pub fn get_phi(&self) -> f32 {
+	pub fn get_phi(&self) -> T {
 		self.phi
 	}
 
-	pub fn get_theta(&self) -> f32 {
+	pub fn get_theta(&self) -> T {
 		self.theta
 	}
 
-	pub fn set_radius(&mut self, v: f32) {
+	pub fn set_radius(&mut self, v: T) {
 		self.radius = v;
 	}
 
-	pub fn set_phi(&mut self, v: f32) {
+	pub fn set_phi(&mut self, v: T) {
 		self.phi = v;
 	}
 
-	pub fn set_theta(&mut self, v: f32) {
+	pub fn set_theta(&mut self, v: T) {
 		self.theta = v;
 	}
 
-	pub fn set(&mut self, radius: f32, phi: f32, theta: f32) {
+	pub fn set(&mut self, radius: T, phi: T, theta: T) {
 		self.radius = radius;
 		self.phi = phi;
 		self.theta = theta;
 	}
 
-	pub fn copy(&mut self, other: &Spherical) {
+	pub fn copy(&mut self, other: &Spherical<T>) {
 		self.radius = other.radius;
 		self.phi = other.phi;
 		self.theta = other.theta;
 	}
 
 	pub fn make_safe(&mut self) {
-		let eps = 0.000001f32;
-		self.phi = eps.max((::std::f32::consts::PI - eps).min(self.phi));
+		let eps = T::from_f64(0.000001);
+		self.phi = eps.max((T::pi() - eps).min(self.phi));
 	}
 
-	pub fn set_from_vector3(&mut self, vec3: Vector3) {
+	pub fn set_from_vector3(&mut self, vec3: Vector3<T>) {
 		self.radius = vec3.length();
 
-		if self.radius == 0.0 {
-			self.theta = 0.0;
-			self.phi = 0.0;
+		if self.radius == T::zero() {
+			self.theta = T::zero();
+			self.phi = T::zero();
 		} else {
 			self.theta = vec3.get_x().atan2( vec3.get_z() ); // equator angle around y-up axis
-			self.phi = clamp( vec3.get_y() / self.radius, - 1.0, 1.0 ).acos(); // polar angle
+			self.phi = clamp( vec3.get_y() / self.radius, - T::one(), T::one() ).acos(); // polar angle
 		}
 	}
-}
\ No newline at end of file
+
+	pub fn cast<U: Float>(&self) -> Spherical<U> {
+		Spherical {
+			radius: self.radius.cast(),
+			phi: self.phi.cast(),
+			theta: self.theta.cast(),
+		}
+	}
+}
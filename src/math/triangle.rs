@@ -0,0 +1,310 @@
+use super::vector2::Vector2;
+use super::vector3::Vector3;
+use super::plane::Plane;
+use super::float::Float;
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Triangle<T = f32> {
+	pub a: Vector3<T>,
+	pub b: Vector3<T>,
+	pub c: Vector3<T>,
+}
+
+impl<T: Float> Triangle<T> {
+	pub fn new() -> Triangle<T> {
+		Triangle {
+			a: Vector3::new(),
+			b: Vector3::new(),
+			c: Vector3::new(),
+		}
+	}
+
+	pub fn get_a(&self) -> &Vector3<T> {
+		&self.a
+	}
+
+	pub fn get_b(&self) -> &Vector3<T> {
+		&self.b
+	}
+
+	pub fn get_c(&self) -> &Vector3<T> {
+		&self.c
+	}
+
+	pub fn set(&mut self, a: &Vector3<T>, b: &Vector3<T>, c: &Vector3<T>) {
+		self.a.copy(a);
+		self.b.copy(b);
+		self.c.copy(c);
+	}
+
+	pub fn set_from_points_and_indices(&mut self, points: &[Vector3<T>], i0: usize, i1: usize, i2: usize) {
+		self.a.copy(&points[i0]);
+		self.b.copy(&points[i1]);
+		self.c.copy(&points[i2]);
+	}
+
+	pub fn copy(&mut self, triangle: &Triangle<T>) {
+		self.a.copy(&triangle.a);
+		self.b.copy(&triangle.b);
+		self.c.copy(&triangle.c);
+	}
+
+	pub fn get_area(&self) -> T {
+		let mut v0 = Vector3::new();
+		let mut v1 = Vector3::new();
+		v0.sub_vectors(&self.c, &self.b);
+		v1.sub_vectors(&self.a, &self.b);
+		v0.cross(&v1);
+
+		v0.length() * T::half()
+	}
+
+	pub fn get_midpoint(&self, target: &mut Vector3<T>) {
+		target.add_vectors(&self.a, &self.b);
+		target.add(&self.c);
+		target.multiply_scalar(T::one() / T::from_f64(3.0));
+	}
+
+	// counter-clockwise winding is front facing. a degenerate triangle gives a zero normal
+	pub fn get_normal(&self, target: &mut Vector3<T>) {
+		let mut v0 = Vector3::new();
+		target.sub_vectors(&self.c, &self.b);
+		v0.sub_vectors(&self.a, &self.b);
+		target.cross(&v0);
+
+		let target_length_sq = target.length_sq();
+		if target_length_sq > T::zero() {
+			target.multiply_scalar(T::one() / target_length_sq.sqrt());
+		} else {
+			target.set(T::zero(), T::zero(), T::zero());
+		}
+	}
+
+	pub fn get_plane(&self, target: &mut Plane<T>) {
+		target.set_from_coplanar_points(&self.a, &self.b, &self.c);
+	}
+
+	// based on http://www.blackpawn.com/texts/pointinpoly/default.html
+	// returns None for a degenerate triangle, where barycentric coordinates are undefined
+	pub fn get_barycoord(&self, point: &Vector3<T>) -> Option<Vector3<T>> {
+		let mut v0 = Vector3::new();
+		let mut v1 = Vector3::new();
+		let mut v2 = Vector3::new();
+		v0.sub_vectors(&self.c, &self.a);
+		v1.sub_vectors(&self.b, &self.a);
+		v2.sub_vectors(point, &self.a);
+
+		let dot00 = v0.dot(&v0);
+		let dot01 = v0.dot(&v1);
+		let dot02 = v0.dot(&v2);
+		let dot11 = v1.dot(&v1);
+		let dot12 = v1.dot(&v2);
+
+		let denom = dot00 * dot11 - dot01 * dot01;
+
+		// collinear or singular triangle
+		if denom == T::zero() {
+			return None;
+		}
+
+		let inv_denom = T::one() / denom;
+		let u = ( dot11 * dot02 - dot01 * dot12 ) * inv_denom;
+		let v = ( dot00 * dot12 - dot01 * dot02 ) * inv_denom;
+
+		// barycentric coordinates must always sum to 1
+		Some(Vector3 {
+			x: T::one() - u - v,
+			y: v,
+			z: u,
+		})
+	}
+
+	pub fn contains_point(&self, point: &Vector3<T>) -> bool {
+		match self.get_barycoord(point) {
+			Some(barycoord) => barycoord.x >= T::zero() && barycoord.y >= T::zero() && ( barycoord.x + barycoord.y ) <= T::one(),
+			None => false,
+		}
+	}
+
+	// interpolates a per-vertex attribute at a point on the triangle
+	pub fn get_uv(&self, point: &Vector3<T>, uv1: &Vector2<T>, uv2: &Vector2<T>, uv3: &Vector2<T>) -> Option<Vector2<T>> {
+		let barycoord = self.get_barycoord(point)?;
+
+		let mut target = Vector2::new();
+		target.add_scaled_vector(uv1, barycoord.x);
+		target.add_scaled_vector(uv2, barycoord.y);
+		target.add_scaled_vector(uv3, barycoord.z);
+		Some(target)
+	}
+
+	pub fn is_front_facing(&self, direction: &Vector3<T>) -> bool {
+		let mut v0 = Vector3::new();
+		let mut v1 = Vector3::new();
+		v0.sub_vectors(&self.c, &self.b);
+		v1.sub_vectors(&self.a, &self.b);
+		v0.cross(&v1);
+
+		// strictly front facing
+		v0.dot(direction) < T::zero()
+	}
+
+	// algorithm from "Real-Time Collision Detection" by Christer Ericson,
+	// published by Morgan Kaufmann Publishers, (c) 2005 Elsevier Inc.
+	pub fn closest_point_to_point(&self, p: &Vector3<T>, target: &mut Vector3<T>) {
+		let a = &self.a;
+		let b = &self.b;
+		let c = &self.c;
+
+		let mut vab = Vector3::new();
+		let mut vac = Vector3::new();
+		let mut vap = Vector3::new();
+		vab.sub_vectors(b, a);
+		vac.sub_vectors(c, a);
+		vap.sub_vectors(p, a);
+
+		let d1 = vab.dot(&vap);
+		let d2 = vac.dot(&vap);
+		if d1 <= T::zero() && d2 <= T::zero() {
+			// vertex region of A
+			target.copy(a);
+			return;
+		}
+
+		let mut vbp = Vector3::new();
+		vbp.sub_vectors(p, b);
+		let d3 = vab.dot(&vbp);
+		let d4 = vac.dot(&vbp);
+		if d3 >= T::zero() && d4 <= d3 {
+			// vertex region of B
+			target.copy(b);
+			return;
+		}
+
+		let vc = d1 * d4 - d3 * d2;
+		if vc <= T::zero() && d1 >= T::zero() && d3 <= T::zero() {
+			// edge region of AB
+			let v = d1 / ( d1 - d3 );
+			target.copy(a);
+			target.add_scaled_vector(&vab, v);
+			return;
+		}
+
+		let mut vcp = Vector3::new();
+		vcp.sub_vectors(p, c);
+		let d5 = vab.dot(&vcp);
+		let d6 = vac.dot(&vcp);
+		if d6 >= T::zero() && d5 <= d6 {
+			// vertex region of C
+			target.copy(c);
+			return;
+		}
+
+		let vb = d5 * d2 - d1 * d6;
+		if vb <= T::zero() && d2 >= T::zero() && d6 <= T::zero() {
+			// edge region of AC
+			let w = d2 / ( d2 - d6 );
+			target.copy(a);
+			target.add_scaled_vector(&vac, w);
+			return;
+		}
+
+		let va = d3 * d6 - d5 * d4;
+		if va <= T::zero() && ( d4 - d3 ) >= T::zero() && ( d5 - d6 ) >= T::zero() {
+			// edge region of BC
+			let mut vbc = Vector3::new();
+			vbc.sub_vectors(c, b);
+			let w = ( d4 - d3 ) / ( ( d4 - d3 ) + ( d5 - d6 ) );
+			target.copy(b);
+			target.add_scaled_vector(&vbc, w);
+			return;
+		}
+
+		// face region
+		let denom = T::one() / ( va + vb + vc );
+		let v = vb * denom;
+		let w = vc * denom;
+
+		target.copy(a);
+		target.add_scaled_vector(&vab, v);
+		target.add_scaled_vector(&vac, w);
+	}
+
+	pub fn equals(&self, triangle: &Triangle<T>) -> bool {
+		triangle.a.equals(&self.a) && triangle.b.equals(&self.b) && triangle.c.equals(&self.c)
+	}
+
+	pub fn cast<U: Float>(&self) -> Triangle<U> {
+		Triangle {
+			a: self.a.cast(),
+			b: self.b.cast(),
+			c: self.c.cast(),
+		}
+	}
+}
+
+impl<T: Float> Default for Triangle<T> {
+	fn default() -> Triangle<T> {
+		Triangle::new()
+	}
+}
+
+impl From<Triangle<f32>> for Triangle<f64> {
+	fn from(triangle: Triangle<f32>) -> Triangle<f64> {
+		triangle.cast()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Triangle;
+	use super::super::vector2::Vector2;
+	use super::super::vector3::Vector3;
+
+	#[test]
+	fn barycoord_drives_containment_and_uv() {
+		let mut triangle = Triangle::new();
+		triangle.set(&Vector3 { x: 0.0, y: 0.0, z: 0.0 }, &Vector3 { x: 2.0, y: 0.0, z: 0.0 }, &Vector3 { x: 0.0, y: 2.0, z: 0.0 });
+
+		assert_eq!(triangle.get_area(), 2.0);
+
+		let mut normal = Vector3::new();
+		triangle.get_normal(&mut normal);
+		assert!(normal.equals(&Vector3 { x: 0.0, y: 0.0, z: 1.0 }));
+		assert!(triangle.is_front_facing(&Vector3 { x: 0.0, y: 0.0, z: - 1.0 }));
+
+		let point = Vector3 { x: 0.5, y: 0.5, z: 0.0 };
+		assert!(triangle.contains_point(&point));
+		assert!(!triangle.contains_point(&Vector3 { x: 1.5, y: 1.5, z: 0.0 }));
+
+		let uv = triangle.get_uv(&point,
+			&Vector2 { x: 0.0, y: 0.0 },
+			&Vector2 { x: 1.0, y: 0.0 },
+			&Vector2 { x: 0.0, y: 1.0 }).unwrap();
+		assert!(uv.equals(&Vector2 { x: 0.25, y: 0.25 }));
+
+		let mut degenerate = Triangle::new();
+		degenerate.set(&Vector3 { x: 0.0, y: 0.0, z: 0.0 }, &Vector3 { x: 1.0, y: 0.0, z: 0.0 }, &Vector3 { x: 2.0, y: 0.0, z: 0.0 });
+		assert!(degenerate.get_barycoord(&point).is_none());
+		assert!(!degenerate.contains_point(&point));
+	}
+
+	#[test]
+	fn closest_point_to_point_finds_each_region() {
+		let mut triangle = Triangle::new();
+		triangle.set(&Vector3 { x: 0.0, y: 0.0, z: 0.0 }, &Vector3 { x: 2.0, y: 0.0, z: 0.0 }, &Vector3 { x: 0.0, y: 2.0, z: 0.0 });
+
+		let mut target = Vector3::new();
+		triangle.closest_point_to_point(&Vector3 { x: - 1.0, y: - 1.0, z: 0.0 }, &mut target);
+		assert!(target.equals(&Vector3 { x: 0.0, y: 0.0, z: 0.0 }));
+
+		triangle.closest_point_to_point(&Vector3 { x: 1.0, y: - 3.0, z: 0.0 }, &mut target);
+		assert!(target.equals(&Vector3 { x: 1.0, y: 0.0, z: 0.0 }));
+
+		triangle.closest_point_to_point(&Vector3 { x: 2.0, y: 2.0, z: 0.0 }, &mut target);
+		assert!(target.equals(&Vector3 { x: 1.0, y: 1.0, z: 0.0 }));
+
+		triangle.closest_point_to_point(&Vector3 { x: 0.5, y: 0.5, z: 3.0 }, &mut target);
+		assert!(target.equals(&Vector3 { x: 0.5, y: 0.5, z: 0.0 }));
+	}
+}
@@ -1,52 +1,57 @@
+use std::ops;
+use super::matrix3::Matrix3;
+use super::float::Float;
+
 #[derive(Debug, Clone, Copy)]
-pub struct Vector2 {
-	pub x: f32,
-	pub y: f32,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vector2<T = f32> {
+	pub x: T,
+	pub y: T,
 }
 
-impl Vector2 {
-	pub fn new() -> Vector2 {
+impl<T: Float> Vector2<T> {
+	pub fn new() -> Vector2<T> {
 		Vector2 {
-			x: 0.0,
-			y: 0.0,
+			x: T::zero(),
+			y: T::zero(),
 		}
 	}
 
-	pub fn get_width(&self) -> f32 {
+	pub fn get_width(&self) -> T {
 		self.x
 	}
 
-	pub fn set_width(&mut self, value: f32) {
+	pub fn set_width(&mut self, value: T) {
 		self.x = value;
 	}
 
-	pub fn get_height(&self) -> f32 {
+	pub fn get_height(&self) -> T {
 		self.y
 	}
 
-	pub fn set_height(&mut self, value: f32) {
+	pub fn set_height(&mut self, value: T) {
 		self.y = value;
 	}
 
-	pub fn set(&mut self, x: f32, y: f32) {
+	pub fn set(&mut self, x: T, y: T) {
 		self.x = x;
 		self.y = y;
 	}
 
-	pub fn set_scalar(&mut self, scalar: f32) {
+	pub fn set_scalar(&mut self, scalar: T) {
 		self.x = scalar;
 		self.y = scalar;
 	}
 
-	pub fn set_x(&mut self, x: f32) {
+	pub fn set_x(&mut self, x: T) {
 		self.x = x;
 	}
 
-	pub fn set_y(&mut self, y: f32) {
+	pub fn set_y(&mut self, y: T) {
 		self.y = y;
 	}
 
-	pub fn set_component(&mut self, index: i32, value: f32) {
+	pub fn set_component(&mut self, index: i32, value: T) {
 		match index {
 			0 => self.x = value,
 			1 => self.y = value,
@@ -54,7 +59,7 @@ impl Vector2 {
 		};
 	}
 
-	pub fn get_component(&mut self, index: i32) -> f32 {
+	pub fn get_component(&mut self, index: i32) -> T {
 		match index {
 			0 => self.x,
 			1 => self.y,
@@ -62,81 +67,90 @@ impl Vector2 {
 		}
 	}
 
-	pub fn add(&mut self, v: &Vector2) {
+	pub fn add(&mut self, v: &Vector2<T>) {
 		self.x += v.x;
 		self.y += v.y;
 	}
 
-	pub fn add_scalar(&mut self, s: f32) {
+	pub fn add_scalar(&mut self, s: T) {
 		self.x += s;
 		self.y += s;
 	}
 
-	pub fn add_vectors(&mut self, a: &Vector2, b: &Vector2) {
+	pub fn add_vectors(&mut self, a: &Vector2<T>, b: &Vector2<T>) {
 		self.x = a.x + b.x;
 		self.y = a.y + b.y;
 	}
 
-	pub fn add_scaled_vector(&mut self, v: &Vector2, s: f32) {
+	pub fn add_scaled_vector(&mut self, v: &Vector2<T>, s: T) {
 		self.x += v.x * s;
 		self.y += v.y * s;
 	}
 
-	pub fn sub(&mut self, v: &Vector2) {
+	pub fn sub(&mut self, v: &Vector2<T>) {
 		self.x -= v.x;
 		self.y -= v.y;
 	}
 
-	pub fn sub_scalar(&mut self, s: f32) {
+	pub fn sub_scalar(&mut self, s: T) {
 		self.x -= s;
 		self.y -= s;
 	}
 
-	pub fn sub_vectors(&mut self, a: &Vector2, b: &Vector2) {
+	pub fn sub_vectors(&mut self, a: &Vector2<T>, b: &Vector2<T>) {
 		self.x = a.x - b.x;
 		self.y = a.y - b.y;
 	}
 
-	pub fn multiply(&mut self, v: &Vector2) {
+	pub fn multiply(&mut self, v: &Vector2<T>) {
 		self.x *= v.x;
 		self.y *= v.y;
 	}
 
-	pub fn multiply_scalar(&mut self, scalar: f32) {
+	pub fn multiply_scalar(&mut self, scalar: T) {
 		if scalar.is_finite() {
 			self.x *= scalar;
 			self.y *= scalar;
 		} else {
-			self.x = 0.0;
-			self.y = 0.0;
+			self.x = T::zero();
+			self.y = T::zero();
 		}
 	}
 
-	pub fn divide(&mut self, v: &Vector2) {
+	// treats the vector as a point, (x, y, 1), under a 2D affine transform
+	pub fn apply_matrix3(&mut self, m: &Matrix3<T>) {
+		let x = self.x;
+		let y = self.y;
+		let e = m.get_elements();
+		self.x = e[ 0 ] * x + e[ 3 ] * y + e[ 6 ];
+		self.y = e[ 1 ] * x + e[ 4 ] * y + e[ 7 ];
+	}
+
+	pub fn divide(&mut self, v: &Vector2<T>) {
 		self.x /= v.x;
 		self.y /= v.y;
 	}
 
-	pub fn divide_scalar(&mut self, scalar: f32) {
-		self.multiply_scalar(1.0 / scalar);
+	pub fn divide_scalar(&mut self, scalar: T) {
+		self.multiply_scalar(T::one() / scalar);
 	}
 
-	pub fn min(&mut self, v: &Vector2) {
+	pub fn min(&mut self, v: &Vector2<T>) {
 		self.x = self.x.min(v.x);
 		self.y = self.y.min(v.y);
 	}
 
-	pub fn max(&mut self, v: &Vector2) {
+	pub fn max(&mut self, v: &Vector2<T>) {
 		self.x = self.x.max(v.x);
 		self.y = self.y.max(v.y);
 	}
 
-	pub fn clamp(&mut self, min: &Vector2, max: &Vector2) {
+	pub fn clamp(&mut self, min: &Vector2<T>, max: &Vector2<T>) {
 		self.x = min.x.max(max.x.min(self.x));
 		self.y = min.y.max(max.y.min(self.y));
 	}
 
-	pub fn clamp_scalar(&mut self, min_val: f32, max_val: f32) {
+	pub fn clamp_scalar(&mut self, min_val: T, max_val: T) {
 		self.clamp(&Vector2 {
 			x: min_val,
 			y: min_val,
@@ -146,7 +160,7 @@ impl Vector2 {
 		});
 	}
 
-	pub fn clamp_length(&mut self, min: f32, max: f32) {
+	pub fn clamp_length(&mut self, min: T, max: T) {
 		let length = self.length();
 
 		self.multiply_scalar(min.max(max.min(length)) / length);
@@ -168,12 +182,12 @@ impl Vector2 {
 	}
 
 	pub fn round_to_zero(&mut self) {
-		self.x = if self.x < 0.0 {
+		self.x = if self.x < T::zero() {
 			self.x.ceil()
 		} else {
 			self.x.floor()
 		};
-		self.y = if self.y < 0.0 {
+		self.y = if self.y < T::zero() {
 			self.y.ceil()
 		} else {
 			self.y.floor()
@@ -185,19 +199,19 @@ impl Vector2 {
 		self.y = -self.y;
 	}
 
-	pub fn dot(&self, v: &Vector2) -> f32 {
+	pub fn dot(&self, v: &Vector2<T>) -> T {
 		(self.x * v.x) + (self.y * v.y)
 	}
 
-	pub fn length_sq(&self) -> f32 {
+	pub fn length_sq(&self) -> T {
 		(self.x * self.x) + (self.y * self.y)
 	}
 
-	pub fn length(&self) -> f32 {
+	pub fn length(&self) -> T {
 		self.length_sq().sqrt()
 	}
 
-	pub fn length_manhattan(&self) -> f32 {
+	pub fn length_manhattan(&self) -> T {
 		self.x.abs() + self.y.abs()
 	}
 
@@ -208,51 +222,51 @@ impl Vector2 {
 		self.divide_scalar(length)
 	}
 
-	pub fn angle(&self) -> f32 {
+	pub fn angle(&self) -> T {
 		let mut angle = self.y.atan2(self.x);
-		if angle < 0.0 {
-			angle += 2.0 * ::std::f32::consts::PI;
+		if angle < T::zero() {
+			angle += T::two() * T::pi();
 		}
 		angle
 	}
 
-	pub fn distance_to(&self, v: &Vector2) -> f32 {
+	pub fn distance_to(&self, v: &Vector2<T>) -> T {
 		self.distance_to_squared(v).sqrt()
 	}
 
-	pub fn distance_to_squared(&self, v: &Vector2) -> f32 {
+	pub fn distance_to_squared(&self, v: &Vector2<T>) -> T {
 		let dx = self.x - v.x;
 		let dy = self.y - v.y;
 		(dx * dx) + (dy * dy)
 	}
 
-	pub fn distance_to_manhattan(&self, v: &Vector2) -> f32 {
+	pub fn distance_to_manhattan(&self, v: &Vector2<T>) -> T {
 		((self.x - v.x).abs()) + ((self.y - v.y).abs())
 	}
 
-	pub fn set_length(&mut self, length: f32) {
+	pub fn set_length(&mut self, length: T) {
 		let l = {
 			length / self.length()
 		};
 		self.multiply_scalar(l);
 	}
 
-	pub fn lerp(&mut self, v: &Vector2, alpha: f32) {
+	pub fn lerp(&mut self, v: &Vector2<T>, alpha: T) {
 		self.x += (v.x - self.x) * alpha;
 		self.y += (v.y - self.y) * alpha;
 	}
 
-	pub fn lerp_vectors(&mut self, v1: &Vector2, v2: &Vector2, alpha: f32) {
+	pub fn lerp_vectors(&mut self, v1: &Vector2<T>, v2: &Vector2<T>, alpha: T) {
 		self.sub_vectors(v2, v1);
 		self.multiply_scalar(alpha);
 		self.add(v1);
 	}
 
-	pub fn equals(&self, v: &Vector2) -> bool {
+	pub fn equals(&self, v: &Vector2<T>) -> bool {
 		(v.x == self.x) && (v.y == self.y)
 	}
 
-	pub fn copy_from_array(&mut self, array: &[f32], offset: Option<usize>) {
+	pub fn copy_from_array(&mut self, array: &[T], offset: Option<usize>) {
 		let offset = match offset {
 			Some(off) => off,
 			None => 0usize,
@@ -261,7 +275,7 @@ impl Vector2 {
 		self.y = array[offset + 1];
 	}
 
-	pub fn copy_to_array(&self, array: &mut [f32], offset: Option<usize>) {
+	pub fn copy_to_array(&self, array: &mut [T], offset: Option<usize>) {
 		let offset = match offset {
 			Some(off) => off,
 			None => 0usize,
@@ -270,7 +284,7 @@ impl Vector2 {
 		array[offset + 1] = self.y;
 	}
 
-	pub fn rotate_around(&mut self, center: &Vector2, angle: f32) {
+	pub fn rotate_around(&mut self, center: &Vector2<T>, angle: T) {
 		let c = angle.cos();
 		let s = angle.sin();
 
@@ -281,9 +295,228 @@ impl Vector2 {
 		self.y = (x * s) + (y * c) + center.y;
 	}
 
-	pub fn copy(&mut self, v: &Vector2) {
+	pub fn copy(&mut self, v: &Vector2<T>) {
 		self.x = v.x;
 		self.y = v.y;
 	}
 
+	pub fn cast<U: Float>(&self) -> Vector2<U> {
+		Vector2 {
+			x: self.x.cast(),
+			y: self.y.cast(),
+		}
+	}
+
+}
+
+impl From<Vector2<f32>> for Vector2<f64> {
+	fn from(v: Vector2<f32>) -> Vector2<f64> {
+		v.cast()
+	}
+}
+
+impl<T: Float> ops::Add<Vector2<T>> for Vector2<T> {
+	type Output = Vector2<T>;
+
+	fn add(self, rhs: Vector2<T>) -> Vector2<T> {
+		let mut v = self;
+		Vector2::add(&mut v, &rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Sub<Vector2<T>> for Vector2<T> {
+	type Output = Vector2<T>;
+
+	fn sub(self, rhs: Vector2<T>) -> Vector2<T> {
+		let mut v = self;
+		Vector2::sub(&mut v, &rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Mul<Vector2<T>> for Vector2<T> {
+	type Output = Vector2<T>;
+
+	fn mul(self, rhs: Vector2<T>) -> Vector2<T> {
+		let mut v = self;
+		v.multiply(&rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Mul<T> for Vector2<T> {
+	type Output = Vector2<T>;
+
+	fn mul(self, rhs: T) -> Vector2<T> {
+		let mut v = self;
+		v.multiply_scalar(rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Div<Vector2<T>> for Vector2<T> {
+	type Output = Vector2<T>;
+
+	fn div(self, rhs: Vector2<T>) -> Vector2<T> {
+		let mut v = self;
+		v.divide(&rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Div<T> for Vector2<T> {
+	type Output = Vector2<T>;
+
+	fn div(self, rhs: T) -> Vector2<T> {
+		let mut v = self;
+		v.divide_scalar(rhs);
+		v
+	}
+}
+
+impl ops::Mul<Vector2<f32>> for f32 {
+	type Output = Vector2<f32>;
+
+	fn mul(self, rhs: Vector2<f32>) -> Vector2<f32> {
+		rhs * self
+	}
+}
+
+impl ops::Mul<Vector2<f64>> for f64 {
+	type Output = Vector2<f64>;
+
+	fn mul(self, rhs: Vector2<f64>) -> Vector2<f64> {
+		rhs * self
+	}
+}
+
+impl<T: Float> ops::Neg for Vector2<T> {
+	type Output = Vector2<T>;
+
+	fn neg(self) -> Vector2<T> {
+		let mut v = self;
+		v.negate();
+		v
+	}
+}
+
+impl<T: Float> ops::AddAssign<Vector2<T>> for Vector2<T> {
+	fn add_assign(&mut self, rhs: Vector2<T>) {
+		Vector2::add(self, &rhs);
+	}
+}
+
+impl<T: Float> ops::SubAssign<Vector2<T>> for Vector2<T> {
+	fn sub_assign(&mut self, rhs: Vector2<T>) {
+		Vector2::sub(self, &rhs);
+	}
+}
+
+impl<T: Float> ops::MulAssign<Vector2<T>> for Vector2<T> {
+	fn mul_assign(&mut self, rhs: Vector2<T>) {
+		self.multiply(&rhs);
+	}
+}
+
+impl<T: Float> ops::MulAssign<T> for Vector2<T> {
+	fn mul_assign(&mut self, rhs: T) {
+		self.multiply_scalar(rhs);
+	}
+}
+
+impl<T: Float> ops::DivAssign<Vector2<T>> for Vector2<T> {
+	fn div_assign(&mut self, rhs: Vector2<T>) {
+		self.divide(&rhs);
+	}
+}
+
+impl<T: Float> ops::DivAssign<T> for Vector2<T> {
+	fn div_assign(&mut self, rhs: T) {
+		self.divide_scalar(rhs);
+	}
+}
+
+impl<T: Float> ops::Index<usize> for Vector2<T> {
+	type Output = T;
+
+	fn index(&self, index: usize) -> &T {
+		match index {
+			0 => &self.x,
+			1 => &self.y,
+			_ => panic!("index out of range: {:?}", index)
+		}
+	}
+}
+
+impl<T: Float> ops::IndexMut<usize> for Vector2<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T {
+		match index {
+			0 => &mut self.x,
+			1 => &mut self.y,
+			_ => panic!("index out of range: {:?}", index)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Vector2;
+
+	#[test]
+	fn operators_match_methods() {
+		let a: Vector2 = Vector2 { x: 1.5, y: - 2.0 };
+		let b = Vector2 { x: 0.5, y: 4.0 };
+
+		let mut expected = a;
+		expected.add(&b);
+		assert!((a + b).equals(&expected));
+		let mut v = a;
+		v += b;
+		assert!(v.equals(&expected));
+
+		expected = a;
+		expected.sub(&b);
+		assert!((a - b).equals(&expected));
+		v = a;
+		v -= b;
+		assert!(v.equals(&expected));
+
+		expected = a;
+		expected.multiply(&b);
+		assert!((a * b).equals(&expected));
+		v = a;
+		v *= b;
+		assert!(v.equals(&expected));
+
+		expected = a;
+		expected.multiply_scalar(3.0);
+		assert!((a * 3.0).equals(&expected));
+		assert!((3.0 * a).equals(&expected));
+		v = a;
+		v *= 3.0;
+		assert!(v.equals(&expected));
+
+		expected = a;
+		expected.divide(&b);
+		assert!((a / b).equals(&expected));
+		v = a;
+		v /= b;
+		assert!(v.equals(&expected));
+
+		expected = a;
+		expected.divide_scalar(2.0);
+		assert!((a / 2.0).equals(&expected));
+		v = a;
+		v /= 2.0;
+		assert!(v.equals(&expected));
+
+		expected = a;
+		expected.negate();
+		assert!((- a).equals(&expected));
+
+		v = a;
+		v[1] = 7.0;
+		assert_eq!((v[0], v[1]), (a.x, 7.0));
+	}
 }
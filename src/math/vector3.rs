@@ -2,63 +2,68 @@ use super::matrix4::Matrix4;
 use super::quaternion::Quaternion;
 use super::euler::Euler;
 use super::matrix3::Matrix3;
-use super::super::cameras::camera::Camera;
+use super::super::cameras::camera::HasCamera;
 use super::math_static::clamp;
 use super::spherical::Spherical;
+use super::cylindrical::Cylindrical;
+use super::float::Float;
+use super::super::errors::SingularMatrixError;
+use std::ops;
 
 #[derive(Debug, Clone, Copy)]
-pub struct Vector3 {
-	pub x: f32,
-	pub y: f32,
-	pub z: f32,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vector3<T = f32> {
+	pub x: T,
+	pub y: T,
+	pub z: T,
 }
 
-impl Vector3 {
-	pub fn new() -> Vector3 {
+impl<T: Float> Vector3<T> {
+	pub fn new() -> Vector3<T> {
 		Vector3 {
-			x: 0.0,
-			y: 0.0,
-			z: 0.0,
+			x: T::zero(),
+			y: T::zero(),
+			z: T::zero(),
 		}
 	}
 
-	pub fn get_x(&self) -> f32 {
+	pub fn get_x(&self) -> T {
 		self.x
 	}
 
-	pub fn set_x(&mut self, x: f32) {
+	pub fn set_x(&mut self, x: T) {
 		self.x = x;
 	}
 
-	pub fn get_y(&self) -> f32 {
+	pub fn get_y(&self) -> T {
 		self.y
 	}
 
-	pub fn set_y(&mut self, y: f32) {
+	pub fn set_y(&mut self, y: T) {
 		self.y = y;
 	}
 
-	pub fn get_z(&self) -> f32 {
+	pub fn get_z(&self) -> T {
 		self.z
 	}
 
-	pub fn set_z(&mut self, z: f32) {
+	pub fn set_z(&mut self, z: T) {
 		self.z = z;
 	}
 
-	pub fn set(&mut self, x: f32, y: f32, z: f32) {
+	pub fn set(&mut self, x: T, y: T, z: T) {
 		self.x = x;
 		self.y = y;
 		self.z = z;
 	}
 
-	pub fn set_scalar(&mut self, scalar: f32) {
+	pub fn set_scalar(&mut self, scalar: T) {
 		self.x = scalar;
 		self.y = scalar;
 		self.z = scalar;
 	}
 
-	pub fn set_component(&mut self, index: i32, value: f32) {
+	pub fn set_component(&mut self, index: i32, value: T) {
 		match index {
 			0 => self.x = value,
 			1 => self.y = value,
@@ -67,7 +72,7 @@ impl Vector3 {
 		};
 	}
 
-	pub fn get_component(&mut self, index: i32) -> f32 {
+	pub fn get_component(&mut self, index: i32) -> T {
 		match index {
 			0 => self.x,
 			1 => self.y,
@@ -76,85 +81,85 @@ impl Vector3 {
 		}
 	}
 
-	pub fn add(&mut self, v: &Vector3) {
+	pub fn add(&mut self, v: &Vector3<T>) {
 		self.x += v.x;
 		self.y += v.y;
 		self.z += v.z;
 	}
 
-	pub fn add_scalar(&mut self, s: f32) {
+	pub fn add_scalar(&mut self, s: T) {
 		self.x += s;
 		self.y += s;
 		self.z += s;
 	}
 
-	pub fn add_vectors(&mut self, a: &Vector3, b: &Vector3) {
+	pub fn add_vectors(&mut self, a: &Vector3<T>, b: &Vector3<T>) {
 		self.x = a.x + b.x;
 		self.y = a.y + b.y;
 		self.z = a.z + b.z;
 	}
 
-	pub fn add_scaled_vector(&mut self, v: &Vector3, s: f32) {
+	pub fn add_scaled_vector(&mut self, v: &Vector3<T>, s: T) {
 		self.x += v.x * s;
 		self.y += v.y * s;
 		self.z += v.z * s;
 	}
 
-	pub fn sub(&mut self, v: &Vector3) {
+	pub fn sub(&mut self, v: &Vector3<T>) {
 		self.x -= v.x;
 		self.y -= v.y;
 		self.z -= v.z;
 	}
 
-	pub fn sub_scalar(&mut self, s: f32) {
+	pub fn sub_scalar(&mut self, s: T) {
 		self.x -= s;
 		self.y -= s;
 		self.z -= s;
 	}
 
-	pub fn sub_vectors(&mut self, a: &Vector3, b: &Vector3) {
+	pub fn sub_vectors(&mut self, a: &Vector3<T>, b: &Vector3<T>) {
 		self.x = a.x - b.x;
 		self.y = a.y - b.y;
 		self.z = a.z - b.z;
 	}
 
-	pub fn multiply(&mut self, v: &Vector3) {
+	pub fn multiply(&mut self, v: &Vector3<T>) {
 		self.x *= v.x;
 		self.y *= v.y;
 		self.z *= v.z;
 	}
 
-	pub fn multiply_scalar(&mut self, scalar: f32) {
+	pub fn multiply_scalar(&mut self, scalar: T) {
 		if scalar.is_finite() {
 			self.x *= scalar;
 			self.y *= scalar;
 			self.z *= scalar;
 		} else {
-			self.x = 0.0;
-			self.y = 0.0;
-			self.z = 0.0;
+			self.x = T::zero();
+			self.y = T::zero();
+			self.z = T::zero();
 		}
 	}
 
-	pub fn multiply_vectors(&mut self, a: &Vector3, b: &Vector3) {
+	pub fn multiply_vectors(&mut self, a: &Vector3<T>, b: &Vector3<T>) {
 		self.x = a.x * b.x;
 		self.y = a.y * b.y;
 		self.z = a.z * b.z;
 	}
 
-	pub fn apply_euler(&mut self, euler: &Euler) {
+	pub fn apply_euler(&mut self, euler: &Euler<T>) {
 		let mut quaternion = Quaternion::new();
 		quaternion.set_from_euler(euler);
 		self.apply_quaternion(&quaternion);
 	}
 
-	pub fn apply_axis_angle(&mut self, axis: &Vector3, angle: f32) {
+	pub fn apply_axis_angle(&mut self, axis: &Vector3<T>, angle: T) {
 		let mut quaternion = Quaternion::new();
 		quaternion.set_from_axis_angle(axis, angle);
 		self.apply_quaternion(&quaternion);
 	}
 
-	pub fn apply_matrix3(&mut self, m: &Matrix3) {
+	pub fn apply_matrix3(&mut self, m: &Matrix3<T>) {
 		let x = self.x;
 		let y = self.y;
 		let z = self.z;
@@ -165,7 +170,7 @@ impl Vector3 {
 
 	}
 	
-	pub fn apply_matrix4(&mut self, m: &Matrix4) {
+	pub fn apply_matrix4(&mut self, m: &Matrix4<T>) {
 		let x = self.x;
 		let y = self.y;
 		let z = self.z;
@@ -175,19 +180,19 @@ impl Vector3 {
 		self.z = e[ 2 ] * x + e[ 6 ] * y + e[ 10 ] * z + e[ 14 ];
 	}
 
-	pub fn apply_projection(&mut self, m: &Matrix4) {
+	pub fn apply_projection(&mut self, m: &Matrix4<T>) {
 		let x = self.x;
 		let y = self.y;
 		let z = self.z;
 		let e = m.get_elements();
-		let d = 1.0 / ( e[ 3 ] * x + e[ 7 ] * y + e[ 11 ] * z + e[ 15 ] ); // perspective divide
+		let d = T::one() / ( e[ 3 ] * x + e[ 7 ] * y + e[ 11 ] * z + e[ 15 ] ); // perspective divide
 
 		self.x = ( e[ 0 ] * x + e[ 4 ] * y + e[ 8 ]  * z + e[ 12 ] ) * d;
 		self.y = ( e[ 1 ] * x + e[ 5 ] * y + e[ 9 ]  * z + e[ 13 ] ) * d;
 		self.z = ( e[ 2 ] * x + e[ 6 ] * y + e[ 10 ] * z + e[ 14 ] ) * d;
 	}
 
-	pub fn apply_quaternion(&mut self, q: &Quaternion) {
+	pub fn apply_quaternion(&mut self, q: &Quaternion<T>) {
 		let x = self.x;
 		let y = self.y;
 		let z = self.z;
@@ -210,23 +215,27 @@ impl Vector3 {
 		self.z = iz * qw + iw * - qz + ix * - qy - iy * - qx;
 	}
 
-	pub fn project(&mut self, camera: &Camera) {
+	pub fn project<C: HasCamera>(&mut self, camera: &C) -> Result<(), SingularMatrixError> {
+		let camera = camera.get_camera();
 		let mut matrix = Matrix4::new();
-		matrix.multiply_matrices(camera.get_projection_matrix(), camera.get_matrix_world_inverse());
+		matrix.multiply_matrices(&camera.get_projection_matrix().cast(), &camera.try_matrix_world_inverse()?.cast());
 		self.apply_projection( &matrix );
+		Ok(())
 	}
 
-	pub fn unproject(&mut self, camera: &Camera) {
+	pub fn unproject<C: HasCamera>(&mut self, camera: &C) -> Result<(), SingularMatrixError> {
+		let camera = camera.get_camera();
 		let mut matrix = Matrix4::new();
 		let mut matrix1 = Matrix4::new();
 		let mut matrix2 = Matrix4::new();
-		matrix1.get_inverse(camera.get_matrix_world_inverse(), false);
-		matrix2.get_inverse(camera.get_projection_matrix(), false);
+		matrix1.get_inverse(&camera.try_matrix_world_inverse()?.cast())?;
+		matrix2.get_inverse(&camera.get_projection_matrix().cast())?;
 		matrix.multiply_matrices(&matrix1, &matrix2);
 		self.apply_projection(&matrix);
+		Ok(())
 	}
 
-	pub fn transform_direction(&mut self, m: &Matrix4) {
+	pub fn transform_direction(&mut self, m: &Matrix4<T>) {
 		let x = self.x;
 		let y = self.y;
 		let z = self.z;
@@ -238,35 +247,35 @@ impl Vector3 {
 		self.normalize();
 	}
 
-	pub fn divide(&mut self, v: &Vector3) {
+	pub fn divide(&mut self, v: &Vector3<T>) {
 		self.x /= v.x;
 		self.y /= v.y;
 		self.z /= v.z;
 	}
 
-	pub fn divide_scalar(&mut self, scalar: f32) {
-		self.multiply_scalar(1.0 / scalar);
+	pub fn divide_scalar(&mut self, scalar: T) {
+		self.multiply_scalar(T::one() / scalar);
 	}
 
-	pub fn min(&mut self, v: &Vector3) {
+	pub fn min(&mut self, v: &Vector3<T>) {
 		self.x = self.x.min(v.x);
 		self.y = self.y.min(v.y);
 		self.z = self.z.min(v.z);
 	}
 
-	pub fn max(&mut self, v: &Vector3) {
+	pub fn max(&mut self, v: &Vector3<T>) {
 		self.x = self.x.max(v.x);
 		self.y = self.y.max(v.y);
 		self.z = self.z.max(v.z);
 	}
 
-	pub fn clamp(&mut self, min: &Vector3, max: &Vector3) {
+	pub fn clamp(&mut self, min: &Vector3<T>, max: &Vector3<T>) {
 		self.x = min.x.max(max.x.min(self.x));
 		self.y = min.y.max(max.y.min(self.y));
 		self.z = min.z.max(max.z.min(self.z));
 	}
 
-	pub fn clamp_scalar(&mut self, min_val: f32, max_val: f32) {
+	pub fn clamp_scalar(&mut self, min_val: T, max_val: T) {
 		self.clamp(&Vector3 {
 			x: min_val,
 			y: min_val,
@@ -278,7 +287,7 @@ impl Vector3 {
 		});
 	}
 
-	pub fn clamp_length(&mut self, min: f32, max: f32) {
+	pub fn clamp_length(&mut self, min: T, max: T) {
 		let length = self.length();
 
 		self.multiply_scalar(min.max(max.min(length)) / length);
@@ -303,17 +312,17 @@ impl Vector3 {
 	}
 
 	pub fn round_to_zero(&mut self) {
-		self.x = if self.x < 0.0 {
+		self.x = if self.x < T::zero() {
 			self.x.ceil()
 		} else {
 			self.x.floor()
 		};
-		self.y = if self.y < 0.0 {
+		self.y = if self.y < T::zero() {
 			self.y.ceil()
 		} else {
 			self.y.floor()
 		};
-		self.z = if self.z < 0.0 {
+		self.z = if self.z < T::zero() {
 			self.z.ceil()
 		} else {
 			self.z.floor()
@@ -326,19 +335,19 @@ impl Vector3 {
 		self.z = -self.z;
 	}
 
-	pub fn dot(&self, v: &Vector3) -> f32 {
+	pub fn dot(&self, v: &Vector3<T>) -> T {
 		(self.x * v.x) + (self.y * v.y) + (self.z * v.z)
 	}
 
-	pub fn length_sq(&self) -> f32 {
+	pub fn length_sq(&self) -> T {
 		(self.x * self.x) + (self.y * self.y) + (self.z * self.z)
 	}
 
-	pub fn length(&self) -> f32 {
+	pub fn length(&self) -> T {
 		self.length_sq().sqrt()
 	}
 
-	pub fn length_manhattan(&self) -> f32 {
+	pub fn length_manhattan(&self) -> T {
 		self.x.abs() + self.y.abs() + self.z.abs()
 	}
 
@@ -349,41 +358,41 @@ impl Vector3 {
 		self.divide_scalar(length)
 	}
 
-	pub fn distance_to(&self, v: &Vector3) -> f32 {
+	pub fn distance_to(&self, v: &Vector3<T>) -> T {
 		self.distance_to_squared(v).sqrt()
 	}
 
-	pub fn distance_to_squared(&self, v: &Vector3) -> f32 {
+	pub fn distance_to_squared(&self, v: &Vector3<T>) -> T {
 		let dx = self.x - v.x;
 		let dy = self.y - v.y;
 		let dz = self.z - v.z;		
 		(dx * dx) + (dy * dy) + (dz * dz)
 	}
 
-	pub fn distance_to_manhattan(&self, v: &Vector3) -> f32 {
+	pub fn distance_to_manhattan(&self, v: &Vector3<T>) -> T {
 		((self.x - v.x).abs()) + ((self.y - v.y).abs()) + ((self.z - v.z).abs())
 	}
 
-	pub fn set_length(&mut self, length: f32) {
+	pub fn set_length(&mut self, length: T) {
 		let l = {
 			length / self.length()
 		};
 		self.multiply_scalar(l);
 	}
 
-	pub fn lerp(&mut self, v: &Vector3, alpha: f32) {
+	pub fn lerp(&mut self, v: &Vector3<T>, alpha: T) {
 		self.x += (v.x - self.x) * alpha;
 		self.y += (v.y - self.y) * alpha;
 		self.z += (v.z - self.z) * alpha;
 	}
 
-	pub fn lerp_vectors(&mut self, v1: &Vector3, v2: &Vector3, alpha: f32) {
+	pub fn lerp_vectors(&mut self, v1: &Vector3<T>, v2: &Vector3<T>, alpha: T) {
 		self.sub_vectors(v2, v1);
 		self.multiply_scalar(alpha);
 		self.add(v1);
 	}
 
-	pub fn cross(&mut self, v: &Vector3) {
+	pub fn cross(&mut self, v: &Vector3<T>) {
 		let x = self.x;
 		let y = self.y;
 		let z = self.z;
@@ -392,7 +401,7 @@ impl Vector3 {
 		self.z = (x * v.y) - (y * v.x);
 	}
 
-	pub fn cross_vectors(&mut self, a: &Vector3, b: &Vector3) {
+	pub fn cross_vectors(&mut self, a: &Vector3<T>, b: &Vector3<T>) {
 		let ax = a.x;
 		let ay = a.y;
 		let az = a.z;
@@ -404,32 +413,32 @@ impl Vector3 {
 		self.z = (ax * by) - (ay * bx);
 	}
 
-	pub fn project_on_vector(&mut self, vector: &Vector3) {
+	pub fn project_on_vector(&mut self, vector: &Vector3<T>) {
 		let scalar = vector.dot(self) / vector.length_sq();
 		self.copy(vector);
 		self.multiply_scalar(scalar);
 	}
 
-	pub fn project_on_plane(&mut self, plane_normal: &Vector3) {
+	pub fn project_on_plane(&mut self, plane_normal: &Vector3<T>) {
 		let mut v1 = Vector3::new();
 		v1.copy(self);
 		v1.project_on_vector(plane_normal);
 		self.sub(&v1);
 	}
 
-	pub fn reflect(&mut self, normal: &Vector3) {
+	pub fn reflect(&mut self, normal: &Vector3<T>) {
 		let mut v1 = Vector3::new();
 		v1.copy(normal);
-		v1.multiply_scalar(2.0 * self.dot(normal));
+		v1.multiply_scalar(T::two() * self.dot(normal));
 		self.sub(&v1);
 	}
 
-	pub fn angle_to(&self, v: &Vector3) -> f32 {
+	pub fn angle_to(&self, v: &Vector3<T>) -> T {
 		let theta = self.dot(v) / ((self.length_sq() * v.length_sq()).sqrt());
-		clamp(theta, -1.0f32, 1.0f32).acos()
+		clamp(theta, - T::one(), T::one()).acos()
 	}
 
-	pub fn set_from_spherical(&mut self, s: &Spherical) {
+	pub fn set_from_spherical(&mut self, s: &Spherical<T>) {
 		let sin_phi_radius = s.get_phi().sin() * s.get_radius();
 
 		self.x = sin_phi_radius * s.get_theta().sin();
@@ -437,11 +446,17 @@ impl Vector3 {
 		self.z = sin_phi_radius * s.get_theta().cos();
 	}
 
-	pub fn set_from_matrix_position(&mut self, m: &Matrix4) {
+	pub fn set_from_cylindrical(&mut self, c: &Cylindrical<T>) {
+		self.x = c.get_radius() * c.get_theta().sin();
+		self.y = c.get_y();
+		self.z = c.get_radius() * c.get_theta().cos();
+	}
+
+	pub fn set_from_matrix_position(&mut self, m: &Matrix4<T>) {
 		self.set_from_matrix_column(m, 3usize);
 	}
 
-	pub fn set_from_matrix_scale(&mut self, m: &Matrix4) {
+	pub fn set_from_matrix_scale(&mut self, m: &Matrix4<T>) {
 		self.set_from_matrix_column(m, 0usize);
 		let sx = self.length();
 		self.set_from_matrix_column(m, 1usize);
@@ -454,15 +469,15 @@ impl Vector3 {
 		self.z = sz;
 	}
 
-	pub fn set_from_matrix_column(&mut self, m: &Matrix4, index: usize) {
+	pub fn set_from_matrix_column(&mut self, m: &Matrix4<T>, index: usize) {
 		self.copy_from_array(m.get_elements(), Some(index * 4usize));
 	}
 
-	pub fn equals(&self, v: &Vector3) -> bool {
+	pub fn equals(&self, v: &Vector3<T>) -> bool {
 		(v.x == self.x) && (v.y == self.y) && (v.z == self.z)
 	}
 
-	pub fn copy_from_array(&mut self, array: &[f32], offset: Option<usize>) {
+	pub fn copy_from_array(&mut self, array: &[T], offset: Option<usize>) {
 		let offset = match offset {
 			Some(off) => off,
 			None => 0usize,
@@ -472,7 +487,7 @@ impl Vector3 {
 		self.z = array[offset + 2];
 	}
 
-	pub fn copy_to_array(&self, array: &mut [f32], offset: Option<usize>) {
+	pub fn copy_to_array(&self, array: &mut [T], offset: Option<usize>) {
 		let offset = match offset {
 			Some(off) => off,
 			None => 0usize,
@@ -482,10 +497,232 @@ impl Vector3 {
 		array[offset + 2] = self.z;
 	}
 
-	pub fn copy(&mut self, v: &Vector3) {
+	pub fn copy(&mut self, v: &Vector3<T>) {
 		self.x = v.x;
 		self.y = v.y;
 		self.z = v.z;
 	}
 
+	pub fn cast<U: Float>(&self) -> Vector3<U> {
+		Vector3 {
+			x: self.x.cast(),
+			y: self.y.cast(),
+			z: self.z.cast(),
+		}
+	}
+
+}
+
+impl From<Vector3<f32>> for Vector3<f64> {
+	fn from(v: Vector3<f32>) -> Vector3<f64> {
+		v.cast()
+	}
+}
+
+impl<T: Float> ops::Add<Vector3<T>> for Vector3<T> {
+	type Output = Vector3<T>;
+
+	fn add(self, rhs: Vector3<T>) -> Vector3<T> {
+		let mut v = self;
+		Vector3::add(&mut v, &rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Sub<Vector3<T>> for Vector3<T> {
+	type Output = Vector3<T>;
+
+	fn sub(self, rhs: Vector3<T>) -> Vector3<T> {
+		let mut v = self;
+		Vector3::sub(&mut v, &rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Mul<Vector3<T>> for Vector3<T> {
+	type Output = Vector3<T>;
+
+	fn mul(self, rhs: Vector3<T>) -> Vector3<T> {
+		let mut v = self;
+		v.multiply(&rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Mul<T> for Vector3<T> {
+	type Output = Vector3<T>;
+
+	fn mul(self, rhs: T) -> Vector3<T> {
+		let mut v = self;
+		v.multiply_scalar(rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Div<Vector3<T>> for Vector3<T> {
+	type Output = Vector3<T>;
+
+	fn div(self, rhs: Vector3<T>) -> Vector3<T> {
+		let mut v = self;
+		v.divide(&rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Div<T> for Vector3<T> {
+	type Output = Vector3<T>;
+
+	fn div(self, rhs: T) -> Vector3<T> {
+		let mut v = self;
+		v.divide_scalar(rhs);
+		v
+	}
+}
+
+impl ops::Mul<Vector3<f32>> for f32 {
+	type Output = Vector3<f32>;
+
+	fn mul(self, rhs: Vector3<f32>) -> Vector3<f32> {
+		rhs * self
+	}
+}
+
+impl ops::Mul<Vector3<f64>> for f64 {
+	type Output = Vector3<f64>;
+
+	fn mul(self, rhs: Vector3<f64>) -> Vector3<f64> {
+		rhs * self
+	}
+}
+
+impl<T: Float> ops::Neg for Vector3<T> {
+	type Output = Vector3<T>;
+
+	fn neg(self) -> Vector3<T> {
+		let mut v = self;
+		v.negate();
+		v
+	}
+}
+
+impl<T: Float> ops::AddAssign<Vector3<T>> for Vector3<T> {
+	fn add_assign(&mut self, rhs: Vector3<T>) {
+		Vector3::add(self, &rhs);
+	}
+}
+
+impl<T: Float> ops::SubAssign<Vector3<T>> for Vector3<T> {
+	fn sub_assign(&mut self, rhs: Vector3<T>) {
+		Vector3::sub(self, &rhs);
+	}
+}
+
+impl<T: Float> ops::MulAssign<Vector3<T>> for Vector3<T> {
+	fn mul_assign(&mut self, rhs: Vector3<T>) {
+		self.multiply(&rhs);
+	}
+}
+
+impl<T: Float> ops::MulAssign<T> for Vector3<T> {
+	fn mul_assign(&mut self, rhs: T) {
+		self.multiply_scalar(rhs);
+	}
+}
+
+impl<T: Float> ops::DivAssign<Vector3<T>> for Vector3<T> {
+	fn div_assign(&mut self, rhs: Vector3<T>) {
+		self.divide(&rhs);
+	}
+}
+
+impl<T: Float> ops::DivAssign<T> for Vector3<T> {
+	fn div_assign(&mut self, rhs: T) {
+		self.divide_scalar(rhs);
+	}
+}
+
+impl<T: Float> ops::Index<usize> for Vector3<T> {
+	type Output = T;
+
+	fn index(&self, index: usize) -> &T {
+		match index {
+			0 => &self.x,
+			1 => &self.y,
+			2 => &self.z,
+			_ => panic!("index out of range: {:?}", index)
+		}
+	}
+}
+
+impl<T: Float> ops::IndexMut<usize> for Vector3<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T {
+		match index {
+			0 => &mut self.x,
+			1 => &mut self.y,
+			2 => &mut self.z,
+			_ => panic!("index out of range: {:?}", index)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Vector3;
+
+	#[test]
+	fn operators_match_methods() {
+		let a: Vector3 = Vector3 { x: 1.5, y: - 2.0, z: 0.25 };
+		let b = Vector3 { x: 0.5, y: 4.0, z: - 8.0 };
+
+		let mut expected = a;
+		expected.add(&b);
+		assert!((a + b).equals(&expected));
+		let mut v = a;
+		v += b;
+		assert!(v.equals(&expected));
+
+		expected = a;
+		expected.sub(&b);
+		assert!((a - b).equals(&expected));
+		v = a;
+		v -= b;
+		assert!(v.equals(&expected));
+
+		expected = a;
+		expected.multiply(&b);
+		assert!((a * b).equals(&expected));
+		v = a;
+		v *= b;
+		assert!(v.equals(&expected));
+
+		expected = a;
+		expected.multiply_scalar(3.0);
+		assert!((a * 3.0).equals(&expected));
+		assert!((3.0 * a).equals(&expected));
+		v = a;
+		v *= 3.0;
+		assert!(v.equals(&expected));
+
+		expected = a;
+		expected.divide(&b);
+		assert!((a / b).equals(&expected));
+		v = a;
+		v /= b;
+		assert!(v.equals(&expected));
+
+		expected = a;
+		expected.divide_scalar(2.0);
+		assert!((a / 2.0).equals(&expected));
+		v = a;
+		v /= 2.0;
+		assert!(v.equals(&expected));
+
+		expected = a;
+		expected.negate();
+		assert!((- a).equals(&expected));
+
+		v = a;
+		v[2] = 7.0;
+		assert_eq!((v[0], v[1], v[2]), (a.x, a.y, 7.0));
+	}
 }
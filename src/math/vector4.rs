@@ -0,0 +1,670 @@
+use super::matrix4::Matrix4;
+use super::quaternion::Quaternion;
+use super::float::Float;
+use std::ops;
+
+// also used for viewport and scissor rectangles, as (x, y, width, height)
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vector4<T = f32> {
+	pub x: T,
+	pub y: T,
+	pub z: T,
+	pub w: T,
+}
+
+impl<T: Float> Vector4<T> {
+	pub fn new() -> Vector4<T> {
+		Vector4 {
+			x: T::zero(),
+			y: T::zero(),
+			z: T::zero(),
+			w: T::one(),
+		}
+	}
+
+	pub fn get_x(&self) -> T {
+		self.x
+	}
+
+	pub fn set_x(&mut self, x: T) {
+		self.x = x;
+	}
+
+	pub fn get_y(&self) -> T {
+		self.y
+	}
+
+	pub fn set_y(&mut self, y: T) {
+		self.y = y;
+	}
+
+	pub fn get_z(&self) -> T {
+		self.z
+	}
+
+	pub fn set_z(&mut self, z: T) {
+		self.z = z;
+	}
+
+	pub fn get_w(&self) -> T {
+		self.w
+	}
+
+	pub fn set_w(&mut self, w: T) {
+		self.w = w;
+	}
+
+	pub fn set(&mut self, x: T, y: T, z: T, w: T) {
+		self.x = x;
+		self.y = y;
+		self.z = z;
+		self.w = w;
+	}
+
+	pub fn set_scalar(&mut self, scalar: T) {
+		self.x = scalar;
+		self.y = scalar;
+		self.z = scalar;
+		self.w = scalar;
+	}
+
+	pub fn set_component(&mut self, index: i32, value: T) {
+		match index {
+			0 => self.x = value,
+			1 => self.y = value,
+			2 => self.z = value,
+			3 => self.w = value,
+			_ => panic!("index out of range: {:?}", index)
+		};
+	}
+
+	pub fn get_component(&self, index: i32) -> T {
+		match index {
+			0 => self.x,
+			1 => self.y,
+			2 => self.z,
+			3 => self.w,
+			_ => panic!("index out of range: {:?}", index)
+		}
+	}
+
+	pub fn add(&mut self, v: &Vector4<T>) {
+		self.x += v.x;
+		self.y += v.y;
+		self.z += v.z;
+		self.w += v.w;
+	}
+
+	pub fn add_scalar(&mut self, s: T) {
+		self.x += s;
+		self.y += s;
+		self.z += s;
+		self.w += s;
+	}
+
+	pub fn add_vectors(&mut self, a: &Vector4<T>, b: &Vector4<T>) {
+		self.x = a.x + b.x;
+		self.y = a.y + b.y;
+		self.z = a.z + b.z;
+		self.w = a.w + b.w;
+	}
+
+	pub fn add_scaled_vector(&mut self, v: &Vector4<T>, s: T) {
+		self.x += v.x * s;
+		self.y += v.y * s;
+		self.z += v.z * s;
+		self.w += v.w * s;
+	}
+
+	pub fn sub(&mut self, v: &Vector4<T>) {
+		self.x -= v.x;
+		self.y -= v.y;
+		self.z -= v.z;
+		self.w -= v.w;
+	}
+
+	pub fn sub_scalar(&mut self, s: T) {
+		self.x -= s;
+		self.y -= s;
+		self.z -= s;
+		self.w -= s;
+	}
+
+	pub fn sub_vectors(&mut self, a: &Vector4<T>, b: &Vector4<T>) {
+		self.x = a.x - b.x;
+		self.y = a.y - b.y;
+		self.z = a.z - b.z;
+		self.w = a.w - b.w;
+	}
+
+	pub fn multiply(&mut self, v: &Vector4<T>) {
+		self.x *= v.x;
+		self.y *= v.y;
+		self.z *= v.z;
+		self.w *= v.w;
+	}
+
+	pub fn multiply_scalar(&mut self, scalar: T) {
+		if scalar.is_finite() {
+			self.x *= scalar;
+			self.y *= scalar;
+			self.z *= scalar;
+			self.w *= scalar;
+		} else {
+			self.x = T::zero();
+			self.y = T::zero();
+			self.z = T::zero();
+			self.w = T::zero();
+		}
+	}
+
+	// keeps w, so clip-space positions can be clipped before the perspective divide
+	pub fn apply_matrix4(&mut self, m: &Matrix4<T>) {
+		let x = self.x;
+		let y = self.y;
+		let z = self.z;
+		let w = self.w;
+		let e = m.get_elements();
+		self.x = e[ 0 ] * x + e[ 4 ] * y + e[ 8 ]  * z + e[ 12 ] * w;
+		self.y = e[ 1 ] * x + e[ 5 ] * y + e[ 9 ]  * z + e[ 13 ] * w;
+		self.z = e[ 2 ] * x + e[ 6 ] * y + e[ 10 ] * z + e[ 14 ] * w;
+		self.w = e[ 3 ] * x + e[ 7 ] * y + e[ 11 ] * z + e[ 15 ] * w;
+	}
+
+	pub fn divide(&mut self, v: &Vector4<T>) {
+		self.x /= v.x;
+		self.y /= v.y;
+		self.z /= v.z;
+		self.w /= v.w;
+	}
+
+	pub fn divide_scalar(&mut self, scalar: T) {
+		self.multiply_scalar(T::one() / scalar);
+	}
+
+	pub fn set_axis_angle_from_quaternion(&mut self, q: &Quaternion<T>) {
+		// http://www.euclideanspace.com/maths/geometry/rotations/conversions/quaternionToAngle/index.htm
+
+		// q is assumed to be normalized
+		self.w = T::two() * q.w.acos();
+
+		let s = ( T::one() - q.w * q.w ).sqrt();
+
+		if s < T::from_f64(0.0001) {
+			self.x = T::one();
+			self.y = T::zero();
+			self.z = T::zero();
+		} else {
+			self.x = q.x / s;
+			self.y = q.y / s;
+			self.z = q.z / s;
+		}
+	}
+
+	pub fn set_axis_angle_from_rotation_matrix(&mut self, m: &Matrix4<T>) {
+		// http://www.euclideanspace.com/maths/geometry/rotations/conversions/matrixToAngle/index.htm
+
+		// assumes the upper 3x3 of m is a pure rotation matrix (i.e, unscaled)
+		let epsilon = T::from_f64(0.01); // margin to allow for rounding errors
+		let epsilon2 = T::from_f64(0.1); // margin to distinguish between 0 and 180 degrees
+		let diagonal = T::half().sqrt(); // cos(45)
+
+		let te = m.get_elements();
+		let m11 = te[ 0 ];
+		let m12 = te[ 4 ];
+		let m13 = te[ 8 ];
+		let m21 = te[ 1 ];
+		let m22 = te[ 5 ];
+		let m23 = te[ 9 ];
+		let m31 = te[ 2 ];
+		let m32 = te[ 6 ];
+		let m33 = te[ 10 ];
+
+		if ( m12 - m21 ).abs() < epsilon && ( m13 - m31 ).abs() < epsilon && ( m23 - m32 ).abs() < epsilon {
+			// singularity found
+			// first check for identity matrix which must have +1 for all terms
+			// in leading diagonal and zero in other terms
+			if ( m12 + m21 ).abs() < epsilon2 && ( m13 + m31 ).abs() < epsilon2 && ( m23 + m32 ).abs() < epsilon2 &&
+				( m11 + m22 + m33 - T::from_f64(3.0) ).abs() < epsilon2 {
+				// this singularity is identity matrix so angle = 0
+				self.set(T::one(), T::zero(), T::zero(), T::zero());
+				return;
+			}
+
+			// otherwise this singularity is angle = 180
+			let xx = ( m11 + T::one() ) / T::two();
+			let yy = ( m22 + T::one() ) / T::two();
+			let zz = ( m33 + T::one() ) / T::two();
+			let four = T::from_f64(4.0);
+			let xy = ( m12 + m21 ) / four;
+			let xz = ( m13 + m31 ) / four;
+			let yz = ( m23 + m32 ) / four;
+
+			let (x, y, z);
+
+			if xx > yy && xx > zz {
+				// m11 is the largest diagonal term
+				if xx < epsilon {
+					x = T::zero();
+					y = diagonal;
+					z = diagonal;
+				} else {
+					x = xx.sqrt();
+					y = xy / x;
+					z = xz / x;
+				}
+			} else if yy > zz {
+				// m22 is the largest diagonal term
+				if yy < epsilon {
+					x = diagonal;
+					y = T::zero();
+					z = diagonal;
+				} else {
+					y = yy.sqrt();
+					x = xy / y;
+					z = yz / y;
+				}
+			} else {
+				// m33 is the largest diagonal term so base result on this
+				if zz < epsilon {
+					x = diagonal;
+					y = diagonal;
+					z = T::zero();
+				} else {
+					z = zz.sqrt();
+					x = xz / z;
+					y = yz / z;
+				}
+			}
+
+			self.set(x, y, z, T::pi());
+			return;
+		}
+
+		// as we have reached here there are no singularities so we can handle normally
+		let mut s = ( ( m32 - m23 ) * ( m32 - m23 ) +
+			( m13 - m31 ) * ( m13 - m31 ) +
+			( m21 - m12 ) * ( m21 - m12 ) ).sqrt(); // used to normalize
+
+		// prevent divide by zero, should not happen if matrix is orthogonal and should be
+		// caught by singularity test above, but I've left it in just in case
+		if s.abs() < T::from_f64(0.001) {
+			s = T::one();
+		}
+
+		self.x = ( m32 - m23 ) / s;
+		self.y = ( m13 - m31 ) / s;
+		self.z = ( m21 - m12 ) / s;
+		self.w = ( ( m11 + m22 + m33 - T::one() ) / T::two() ).acos();
+	}
+
+	pub fn min(&mut self, v: &Vector4<T>) {
+		self.x = self.x.min(v.x);
+		self.y = self.y.min(v.y);
+		self.z = self.z.min(v.z);
+		self.w = self.w.min(v.w);
+	}
+
+	pub fn max(&mut self, v: &Vector4<T>) {
+		self.x = self.x.max(v.x);
+		self.y = self.y.max(v.y);
+		self.z = self.z.max(v.z);
+		self.w = self.w.max(v.w);
+	}
+
+	pub fn clamp(&mut self, min: &Vector4<T>, max: &Vector4<T>) {
+		self.x = min.x.max(max.x.min(self.x));
+		self.y = min.y.max(max.y.min(self.y));
+		self.z = min.z.max(max.z.min(self.z));
+		self.w = min.w.max(max.w.min(self.w));
+	}
+
+	pub fn clamp_scalar(&mut self, min_val: T, max_val: T) {
+		self.clamp(&Vector4 {
+			x: min_val,
+			y: min_val,
+			z: min_val,
+			w: min_val,
+		}, &Vector4 {
+			x: max_val,
+			y: max_val,
+			z: max_val,
+			w: max_val,
+		});
+	}
+
+	pub fn clamp_length(&mut self, min: T, max: T) {
+		let length = self.length();
+
+		self.multiply_scalar(min.max(max.min(length)) / length);
+	}
+
+	pub fn floor(&mut self) {
+		self.x = self.x.floor();
+		self.y = self.y.floor();
+		self.z = self.z.floor();
+		self.w = self.w.floor();
+	}
+
+	pub fn ceil(&mut self) {
+		self.x = self.x.ceil();
+		self.y = self.y.ceil();
+		self.z = self.z.ceil();
+		self.w = self.w.ceil();
+	}
+
+	pub fn round(&mut self) {
+		self.x = self.x.round();
+		self.y = self.y.round();
+		self.z = self.z.round();
+		self.w = self.w.round();
+	}
+
+	pub fn round_to_zero(&mut self) {
+		self.x = if self.x < T::zero() { self.x.ceil() } else { self.x.floor() };
+		self.y = if self.y < T::zero() { self.y.ceil() } else { self.y.floor() };
+		self.z = if self.z < T::zero() { self.z.ceil() } else { self.z.floor() };
+		self.w = if self.w < T::zero() { self.w.ceil() } else { self.w.floor() };
+	}
+
+	pub fn negate(&mut self) {
+		self.x = -self.x;
+		self.y = -self.y;
+		self.z = -self.z;
+		self.w = -self.w;
+	}
+
+	pub fn dot(&self, v: &Vector4<T>) -> T {
+		(self.x * v.x) + (self.y * v.y) + (self.z * v.z) + (self.w * v.w)
+	}
+
+	pub fn length_sq(&self) -> T {
+		(self.x * self.x) + (self.y * self.y) + (self.z * self.z) + (self.w * self.w)
+	}
+
+	pub fn length(&self) -> T {
+		self.length_sq().sqrt()
+	}
+
+	pub fn length_manhattan(&self) -> T {
+		self.x.abs() + self.y.abs() + self.z.abs() + self.w.abs()
+	}
+
+	pub fn normalize(&mut self) {
+		let length = self.length();
+		self.divide_scalar(length)
+	}
+
+	pub fn set_length(&mut self, length: T) {
+		let l = length / self.length();
+		self.multiply_scalar(l);
+	}
+
+	pub fn lerp(&mut self, v: &Vector4<T>, alpha: T) {
+		self.x += (v.x - self.x) * alpha;
+		self.y += (v.y - self.y) * alpha;
+		self.z += (v.z - self.z) * alpha;
+		self.w += (v.w - self.w) * alpha;
+	}
+
+	pub fn lerp_vectors(&mut self, v1: &Vector4<T>, v2: &Vector4<T>, alpha: T) {
+		self.sub_vectors(v2, v1);
+		self.multiply_scalar(alpha);
+		self.add(v1);
+	}
+
+	pub fn equals(&self, v: &Vector4<T>) -> bool {
+		(v.x == self.x) && (v.y == self.y) && (v.z == self.z) && (v.w == self.w)
+	}
+
+	pub fn copy_from_array(&mut self, array: &[T], offset: Option<usize>) {
+		let offset = match offset {
+			Some(off) => off,
+			None => 0usize,
+		};
+		self.x = array[offset];
+		self.y = array[offset + 1];
+		self.z = array[offset + 2];
+		self.w = array[offset + 3];
+	}
+
+	pub fn copy_to_array(&self, array: &mut [T], offset: Option<usize>) {
+		let offset = match offset {
+			Some(off) => off,
+			None => 0usize,
+		};
+		array[offset] = self.x;
+		array[offset + 1] = self.y;
+		array[offset + 2] = self.z;
+		array[offset + 3] = self.w;
+	}
+
+	pub fn copy(&mut self, v: &Vector4<T>) {
+		self.x = v.x;
+		self.y = v.y;
+		self.z = v.z;
+		self.w = v.w;
+	}
+
+	pub fn cast<U: Float>(&self) -> Vector4<U> {
+		Vector4 {
+			x: self.x.cast(),
+			y: self.y.cast(),
+			z: self.z.cast(),
+			w: self.w.cast(),
+		}
+	}
+}
+
+impl<T: Float> Default for Vector4<T> {
+	fn default() -> Vector4<T> {
+		Vector4::new()
+	}
+}
+
+impl From<Vector4<f32>> for Vector4<f64> {
+	fn from(v: Vector4<f32>) -> Vector4<f64> {
+		v.cast()
+	}
+}
+
+impl<T: Float> ops::Add<Vector4<T>> for Vector4<T> {
+	type Output = Vector4<T>;
+
+	fn add(self, rhs: Vector4<T>) -> Vector4<T> {
+		let mut v = self;
+		Vector4::add(&mut v, &rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Sub<Vector4<T>> for Vector4<T> {
+	type Output = Vector4<T>;
+
+	fn sub(self, rhs: Vector4<T>) -> Vector4<T> {
+		let mut v = self;
+		Vector4::sub(&mut v, &rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Mul<Vector4<T>> for Vector4<T> {
+	type Output = Vector4<T>;
+
+	fn mul(self, rhs: Vector4<T>) -> Vector4<T> {
+		let mut v = self;
+		v.multiply(&rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Mul<T> for Vector4<T> {
+	type Output = Vector4<T>;
+
+	fn mul(self, rhs: T) -> Vector4<T> {
+		let mut v = self;
+		v.multiply_scalar(rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Div<Vector4<T>> for Vector4<T> {
+	type Output = Vector4<T>;
+
+	fn div(self, rhs: Vector4<T>) -> Vector4<T> {
+		let mut v = self;
+		v.divide(&rhs);
+		v
+	}
+}
+
+impl<T: Float> ops::Div<T> for Vector4<T> {
+	type Output = Vector4<T>;
+
+	fn div(self, rhs: T) -> Vector4<T> {
+		let mut v = self;
+		v.divide_scalar(rhs);
+		v
+	}
+}
+
+impl ops::Mul<Vector4<f32>> for f32 {
+	type Output = Vector4<f32>;
+
+	fn mul(self, rhs: Vector4<f32>) -> Vector4<f32> {
+		rhs * self
+	}
+}
+
+impl ops::Mul<Vector4<f64>> for f64 {
+	type Output = Vector4<f64>;
+
+	fn mul(self, rhs: Vector4<f64>) -> Vector4<f64> {
+		rhs * self
+	}
+}
+
+impl<T: Float> ops::Mul<Vector4<T>> for Matrix4<T> {
+	type Output = Vector4<T>;
+
+	fn mul(self, rhs: Vector4<T>) -> Vector4<T> {
+		let mut v = rhs;
+		v.apply_matrix4(&self);
+		v
+	}
+}
+
+impl<T: Float> ops::Neg for Vector4<T> {
+	type Output = Vector4<T>;
+
+	fn neg(self) -> Vector4<T> {
+		let mut v = self;
+		v.negate();
+		v
+	}
+}
+
+impl<T: Float> ops::AddAssign<Vector4<T>> for Vector4<T> {
+	fn add_assign(&mut self, rhs: Vector4<T>) {
+		Vector4::add(self, &rhs);
+	}
+}
+
+impl<T: Float> ops::SubAssign<Vector4<T>> for Vector4<T> {
+	fn sub_assign(&mut self, rhs: Vector4<T>) {
+		Vector4::sub(self, &rhs);
+	}
+}
+
+impl<T: Float> ops::MulAssign<Vector4<T>> for Vector4<T> {
+	fn mul_assign(&mut self, rhs: Vector4<T>) {
+		self.multiply(&rhs);
+	}
+}
+
+impl<T: Float> ops::MulAssign<T> for Vector4<T> {
+	fn mul_assign(&mut self, rhs: T) {
+		self.multiply_scalar(rhs);
+	}
+}
+
+impl<T: Float> ops::DivAssign<Vector4<T>> for Vector4<T> {
+	fn div_assign(&mut self, rhs: Vector4<T>) {
+		self.divide(&rhs);
+	}
+}
+
+impl<T: Float> ops::DivAssign<T> for Vector4<T> {
+	fn div_assign(&mut self, rhs: T) {
+		self.divide_scalar(rhs);
+	}
+}
+
+impl<T: Float> ops::Index<usize> for Vector4<T> {
+	type Output = T;
+
+	fn index(&self, index: usize) -> &T {
+		match index {
+			0 => &self.x,
+			1 => &self.y,
+			2 => &self.z,
+			3 => &self.w,
+			_ => panic!("index out of range: {:?}", index)
+		}
+	}
+}
+
+impl<T: Float> ops::IndexMut<usize> for Vector4<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T {
+		match index {
+			0 => &mut self.x,
+			1 => &mut self.y,
+			2 => &mut self.z,
+			3 => &mut self.w,
+			_ => panic!("index out of range: {:?}", index)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Vector4;
+	use super::super::matrix4::Matrix4;
+	use super::super::quaternion::Quaternion;
+	use super::super::vector3::Vector3;
+
+	#[test]
+	fn apply_matrix4_keeps_w_for_clipping() {
+		let mut m: Matrix4 = Matrix4::new();
+		m.make_perspective(90.0, 1.0, 1.0, 10.0);
+
+		// behind the camera, the divided position would flip onto the screen
+		let clip = m * Vector4 { x: 0.5, y: 0.0, z: 2.0, w: 1.0 };
+		assert!(clip.w < 0.0);
+
+		let clip = m * Vector4 { x: 0.5, y: 0.0, z: - 2.0, w: 1.0 };
+		assert!((clip.w - 2.0).abs() < 1e-6);
+		assert!((clip.x / clip.w - 0.25).abs() < 1e-6);
+	}
+
+	#[test]
+	fn axis_angle_from_quaternion_and_rotation_matrix() {
+		let axis = Vector3 { x: 0.0, y: 0.0, z: 1.0 };
+		let mut q = Quaternion::new();
+		q.set_from_axis_angle(&axis, 1.0);
+
+		let mut v: Vector4 = Vector4::new();
+		v.set_axis_angle_from_quaternion(&q);
+		assert!((v.z - 1.0).abs() < 1e-5 && (v.w - 1.0).abs() < 1e-5);
+
+		let mut m = Matrix4::new();
+		m.make_rotation_axis(&axis, 1.0);
+		v.set_axis_angle_from_rotation_matrix(&m);
+		assert!((v.z - 1.0).abs() < 1e-5 && (v.w - 1.0).abs() < 1e-5);
+
+		m.make_rotation_x(::std::f32::consts::PI);
+		v.set_axis_angle_from_rotation_matrix(&m);
+		assert!((v.x - 1.0).abs() < 1e-5 && (v.w - ::std::f32::consts::PI).abs() < 1e-5);
+	}
+}
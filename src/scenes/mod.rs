@@ -0,0 +1 @@
+pub mod scene;
\ No newline at end of file
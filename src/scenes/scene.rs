@@ -0,0 +1,520 @@
+extern crate uuid;
+#[cfg(feature = "serde")]
+use serde_json::{Map, Value};
+use std::any::Any;
+use std::collections::VecDeque;
+use self::uuid::Uuid;
+use super::super::core::object3d::{Object3D, HasObject3D};
+use super::super::math::matrix4::Matrix4;
+
+pub type SceneObject = dyn HasObject3D + Send + Sync;
+
+// a handle to a node in a scene. the generation tells apart nodes that reuse the slot of a removed one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+	index: usize,
+	generation: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraversalOrder {
+	DepthFirst,
+	BreadthFirst,
+}
+
+struct Node {
+	object: Box<SceneObject>,
+	parent: Option<NodeId>,
+	children: Vec<NodeId>,
+}
+
+struct Slot {
+	generation: u32,
+	node: Option<Node>,
+}
+
+// owns every object in the hierarchy. nodes are reached through NodeIds, and every node
+// except the root has a parent. methods taking a NodeId panic if it isn't in the scene,
+// apart from the lookups that return an Option
+pub struct Scene {
+	slots: Vec<Slot>,
+	free: Vec<usize>,
+	root: NodeId,
+	len: usize,
+}
+
+impl Scene {
+	pub fn new() -> Scene {
+		let mut scene = Scene {
+			slots: vec![],
+			free: vec![],
+			root: NodeId {
+				index: 0,
+				generation: 0,
+			},
+			len: 0,
+		};
+		scene.root = scene.insert(Box::new(Object3D::new()), None);
+		scene
+	}
+
+	pub fn get_root(&self) -> NodeId {
+		self.root
+	}
+
+	pub fn get_node_count(&self) -> usize {
+		self.len
+	}
+
+	pub fn contains(&self, id: NodeId) -> bool {
+		self.try_node(id).is_some()
+	}
+
+	pub fn add<O: HasObject3D + Send + Sync + 'static>(&mut self, object: O) -> NodeId {
+		let root = self.root;
+		self.add_to(root, object)
+	}
+
+	pub fn add_to<O: HasObject3D + Send + Sync + 'static>(&mut self, parent: NodeId, object: O) -> NodeId {
+		self.add_boxed_to(parent, Box::new(object))
+	}
+
+	// for objects whose type is only known at runtime, e.g. the ones a loader builds
+	pub fn add_boxed_to(&mut self, parent: NodeId, object: Box<SceneObject>) -> NodeId {
+		self.node(parent);
+		let id = self.insert(object, Some(parent));
+		self.node_mut(parent).children.push(id);
+		id
+	}
+
+	// moves a node and its subtree under a new parent. refuses to make a node its own ancestor
+	pub fn attach(&mut self, child: NodeId, parent: NodeId) -> bool {
+		self.node(child);
+
+		if child == self.root || self.is_ancestor_or_self(child, parent) {
+			return false;
+		}
+
+		if let Some(old_parent) = self.node(child).parent {
+			self.node_mut(old_parent).children.retain(|&c| c != child);
+		}
+
+		self.node_mut(child).parent = Some(parent);
+		self.node_mut(parent).children.push(child);
+		true
+	}
+
+	// removes a node along with its whole subtree. the root can't be removed
+	pub fn remove(&mut self, id: NodeId) -> bool {
+		if id == self.root || !self.contains(id) {
+			return false;
+		}
+
+		if let Some(parent) = self.node(id).parent {
+			self.node_mut(parent).children.retain(|&c| c != id);
+		}
+
+		let mut pending = vec![id];
+		while let Some(id) = pending.pop() {
+			let slot = &mut self.slots[id.index];
+			let node = slot.node.take().unwrap();
+			slot.generation = slot.generation.wrapping_add(1);
+			self.free.push(id.index);
+			self.len -= 1;
+			pending.extend(node.children);
+		}
+
+		true
+	}
+
+	pub fn get(&self, id: NodeId) -> Option<&SceneObject> {
+		self.try_node(id).map(|node| &*node.object)
+	}
+
+	pub fn get_mut(&mut self, id: NodeId) -> Option<&mut SceneObject> {
+		self.try_node_mut(id).map(|node| &mut *node.object)
+	}
+
+	// reaches the concrete type of a node, e.g. a camera to render with
+	pub fn get_as<O: HasObject3D>(&self, id: NodeId) -> Option<&O> {
+		let object: &dyn Any = self.get(id)?;
+		object.downcast_ref()
+	}
+
+	pub fn get_as_mut<O: HasObject3D>(&mut self, id: NodeId) -> Option<&mut O> {
+		let object: &mut dyn Any = self.get_mut(id)?;
+		object.downcast_mut()
+	}
+
+	pub fn get_object3d(&self, id: NodeId) -> Option<&Object3D> {
+		self.get(id).map(|object| object.get_object3d())
+	}
+
+	pub fn get_object3d_mut(&mut self, id: NodeId) -> Option<&mut Object3D> {
+		self.get_mut(id).map(|object| object.get_object3d_mut())
+	}
+
+	pub fn get_parent(&self, id: NodeId) -> Option<NodeId> {
+		self.node(id).parent
+	}
+
+	pub fn get_children(&self, id: NodeId) -> &[NodeId] {
+		&self.node(id).children
+	}
+
+	pub fn iter<'a>(&'a self) -> Nodes<'a> {
+		Nodes {
+			slots: self.slots.iter().enumerate(),
+		}
+	}
+
+	// yields every descendant of a node, not including the node itself
+	pub fn descendants<'a>(&'a self, id: NodeId, order: TraversalOrder) -> Descendants<'a> {
+		Descendants {
+			scene: self,
+			order,
+			pending: self.node(id).children.iter().cloned().collect(),
+		}
+	}
+
+	pub fn traverse<F: FnMut(NodeId, &Object3D)>(&self, id: NodeId, mut callback: F) {
+		callback(id, self.node(id).object.get_object3d());
+
+		for child in self.descendants(id, TraversalOrder::DepthFirst) {
+			callback(child, self.node(child).object.get_object3d());
+		}
+	}
+
+	pub fn traverse_visible<F: FnMut(NodeId, &Object3D)>(&self, id: NodeId, mut callback: F) {
+		self.traverse_visible_with(id, &mut callback);
+	}
+
+	fn traverse_visible_with<F: FnMut(NodeId, &Object3D)>(&self, id: NodeId, callback: &mut F) {
+		let node = self.node(id);
+		let object = node.object.get_object3d();
+
+		if ! object.get_visible() {
+			return;
+		}
+
+		callback(id, object);
+
+		for &child in node.children.iter() {
+			self.traverse_visible_with(child, callback);
+		}
+	}
+
+	pub fn traverse_ancestors<F: FnMut(NodeId, &Object3D)>(&self, id: NodeId, mut callback: F) {
+		let mut parent = self.node(id).parent;
+
+		while let Some(id) = parent {
+			let node = self.node(id);
+			callback(id, node.object.get_object3d());
+			parent = node.parent;
+		}
+	}
+
+	// the lookups search a node and its descendants, depth first
+	pub fn get_object_by_id(&self, id: NodeId, object_id: usize) -> Option<NodeId> {
+		self.find(id, |object| object.get_id() == object_id)
+	}
+
+	pub fn get_object_by_name(&self, id: NodeId, name: &str) -> Option<NodeId> {
+		self.find(id, |object| object.get_name() == name)
+	}
+
+	pub fn get_object_by_uuid(&self, id: NodeId, uuid: &Uuid) -> Option<NodeId> {
+		self.find(id, |object| object.get_uuid() == uuid)
+	}
+
+	fn find<P: Fn(&Object3D) -> bool>(&self, id: NodeId, predicate: P) -> Option<NodeId> {
+		if predicate(self.node(id).object.get_object3d()) {
+			return Some(id);
+		}
+
+		self.descendants(id, TraversalOrder::DepthFirst)
+			.find(|&id| predicate(self.node(id).object.get_object3d()))
+	}
+
+	pub fn update_matrix_world(&mut self, id: NodeId, force: bool) {
+		let parent_matrix_world = self.get_parent_matrix_world(id);
+		let force = self.node_mut(id).object.update_matrix_world_from(parent_matrix_world.as_ref(), force);
+
+		for i in 0..self.node(id).children.len() {
+			let child = self.node(id).children[i];
+			self.update_matrix_world(child, force);
+		}
+	}
+
+	pub fn update_world_matrix(&mut self, id: NodeId, update_parents: bool, update_children: bool) {
+		if update_parents {
+			if let Some(parent) = self.node(id).parent {
+				self.update_world_matrix(parent, true, false);
+			}
+		}
+
+		let parent_matrix_world = self.get_parent_matrix_world(id);
+		self.node_mut(id).object.update_world_matrix_from(parent_matrix_world.as_ref());
+
+		if update_children {
+			for i in 0..self.node(id).children.len() {
+				let child = self.node(id).children[i];
+				self.update_world_matrix(child, false, true);
+			}
+		}
+	}
+
+	// a three.js JSON object document for a node and its subtree, the node's
+	// concrete type is written as "type" along with its own fields
+	#[cfg(feature = "serde")]
+	pub fn to_json(&self, id: NodeId) -> Value {
+		let mut document = self.node(id).object.get_object3d().to_json();
+		document["object"] = Value::Object(self.to_json_object(id));
+		document
+	}
+
+	#[cfg(feature = "serde")]
+	fn to_json_object(&self, id: NodeId) -> Map<String, Value> {
+		let node = self.node(id);
+		let mut object = node.object.get_object3d().to_json_object();
+		object.insert("type".to_string(), Value::from(node.object.get_type()));
+		node.object.write_json_fields(&mut object);
+
+		if !node.children.is_empty() {
+			let children = node.children.iter()
+				.map(|&child| Value::Object(self.to_json_object(child)))
+				.collect::<Vec<_>>();
+			object.insert("children".to_string(), Value::Array(children));
+		}
+
+		object
+	}
+
+	fn get_parent_matrix_world(&self, id: NodeId) -> Option<Matrix4> {
+		self.node(id).parent
+			.map(|parent| *self.node(parent).object.get_object3d().get_matrix_world())
+	}
+
+	fn is_ancestor_or_self(&self, ancestor: NodeId, id: NodeId) -> bool {
+		let mut current = Some(id);
+
+		while let Some(id) = current {
+			if id == ancestor {
+				return true;
+			}
+			current = self.node(id).parent;
+		}
+
+		false
+	}
+
+	fn insert(&mut self, object: Box<SceneObject>, parent: Option<NodeId>) -> NodeId {
+		let node = Node {
+			object,
+			parent,
+			children: vec![],
+		};
+
+		self.len += 1;
+
+		match self.free.pop() {
+			Some(index) => {
+				let slot = &mut self.slots[index];
+				slot.node = Some(node);
+				NodeId {
+					index,
+					generation: slot.generation,
+				}
+			},
+			None => {
+				self.slots.push(Slot {
+					generation: 0,
+					node: Some(node),
+				});
+				NodeId {
+					index: self.slots.len() - 1,
+					generation: 0,
+				}
+			},
+		}
+	}
+
+	fn try_node(&self, id: NodeId) -> Option<&Node> {
+		self.slots.get(id.index)
+			.filter(|slot| slot.generation == id.generation)
+			.and_then(|slot| slot.node.as_ref())
+	}
+
+	fn try_node_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+		self.slots.get_mut(id.index)
+			.filter(|slot| slot.generation == id.generation)
+			.and_then(|slot| slot.node.as_mut())
+	}
+
+	fn node(&self, id: NodeId) -> &Node {
+		self.try_node(id).expect("node is not in the scene")
+	}
+
+	fn node_mut(&mut self, id: NodeId) -> &mut Node {
+		self.try_node_mut(id).expect("node is not in the scene")
+	}
+}
+
+impl Default for Scene {
+	fn default() -> Scene {
+		Scene::new()
+	}
+}
+
+pub struct Nodes<'a> {
+	slots: ::std::iter::Enumerate<::std::slice::Iter<'a, Slot>>,
+}
+
+impl<'a> Iterator for Nodes<'a> {
+	type Item = (NodeId, &'a SceneObject);
+
+	fn next(&mut self) -> Option<(NodeId, &'a SceneObject)> {
+		for (index, slot) in self.slots.by_ref() {
+			if let Some(ref node) = slot.node {
+				let id = NodeId {
+					index,
+					generation: slot.generation,
+				};
+				return Some((id, &*node.object));
+			}
+		}
+
+		None
+	}
+}
+
+pub struct Descendants<'a> {
+	scene: &'a Scene,
+	order: TraversalOrder,
+	pending: VecDeque<NodeId>,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+	type Item = NodeId;
+
+	fn next(&mut self) -> Option<NodeId> {
+		let next = self.pending.pop_front()?;
+		let children = &self.scene.node(next).children;
+
+		match self.order {
+			TraversalOrder::DepthFirst => {
+				for &child in children.iter().rev() {
+					self.pending.push_front(child);
+				}
+			},
+			TraversalOrder::BreadthFirst => {
+				self.pending.extend(children.iter().cloned());
+			},
+		}
+
+		Some(next)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Scene, TraversalOrder};
+	use super::super::super::core::object3d::Object3D;
+	use super::super::super::math::vector3::Vector3;
+
+	fn named(name: &'static str) -> Object3D {
+		let mut object = Object3D::new();
+		object.set_name(name);
+		object
+	}
+
+	#[test]
+	fn scene_is_send_and_sync() {
+		fn assert_send_sync<T: Send + Sync>() {}
+		assert_send_sync::<Scene>();
+	}
+
+	#[test]
+	fn update_matrix_world_propagates_to_children() {
+		let mut scene = Scene::new();
+		let parent = scene.add(Object3D::new());
+		let child = scene.add_to(parent, Object3D::new());
+
+		scene.get_object3d_mut(parent).unwrap().translate_x(2.0);
+		scene.get_object3d_mut(child).unwrap().translate_y(3.0);
+		let root = scene.get_root();
+		scene.update_matrix_world(root, false);
+
+		let mut v = Vector3::new();
+		scene.get_object3d(child).unwrap().local_to_world(&mut v);
+		assert!(v.equals(&Vector3 { x: 2.0, y: 3.0, z: 0.0 }));
+
+		// a targeted update from the child pulls in the moved parent
+		scene.get_object3d_mut(parent).unwrap().translate_x(1.0);
+		scene.update_world_matrix(child, true, false);
+
+		let mut v = Vector3::new();
+		scene.get_object3d(child).unwrap().local_to_world(&mut v);
+		assert!(v.equals(&Vector3 { x: 3.0, y: 3.0, z: 0.0 }));
+	}
+
+	#[test]
+	fn traversal_and_lookup() {
+		let mut scene = Scene::new();
+		let a = scene.add(named("a"));
+		let a1 = scene.add_to(a, named("a1"));
+		let b = scene.add(named("b"));
+		let root = scene.get_root();
+
+		let names = |scene: &Scene, order| scene.descendants(root, order)
+			.map(|id| scene.get_object3d(id).unwrap().get_name().to_string())
+			.collect::<Vec<_>>();
+		assert_eq!(names(&scene, TraversalOrder::DepthFirst), vec!["a", "a1", "b"]);
+		assert_eq!(names(&scene, TraversalOrder::BreadthFirst), vec!["a", "b", "a1"]);
+
+		scene.get_object3d_mut(a).unwrap().set_visible(false);
+		let mut visible = vec![];
+		scene.traverse_visible(root, |id, _| visible.push(id));
+		assert_eq!(visible, vec![root, b]);
+
+		let mut ancestors = vec![];
+		scene.traverse_ancestors(a1, |id, _| ancestors.push(id));
+		assert_eq!(ancestors, vec![a, root]);
+
+		let id = scene.get_object3d(a1).unwrap().get_id();
+		assert_eq!(scene.get_object_by_id(root, id), Some(a1));
+		assert_eq!(scene.get_object_by_id(b, id), None);
+		assert_eq!(scene.get_object_by_name(root, "b"), Some(b));
+		assert_eq!(scene.get_object_by_name(a, "b"), None);
+		assert_eq!(scene.get_object_by_name(a, "a"), Some(a));
+		assert_eq!(scene.get_object_by_name(root, "c"), None);
+
+		let uuid = *scene.get_object3d(a1).unwrap().get_uuid();
+		assert_eq!(scene.get_object_by_uuid(a, &uuid), Some(a1));
+	}
+
+	#[test]
+	fn reparent_and_remove() {
+		let mut scene = Scene::new();
+		let a = scene.add(named("a"));
+		let a1 = scene.add_to(a, named("a1"));
+		let b = scene.add(named("b"));
+
+		assert!(!scene.attach(a, a1));
+		assert!(scene.attach(a1, b));
+		assert_eq!(scene.get_parent(a1), Some(b));
+		assert!(scene.get_children(a).is_empty());
+
+		assert!(scene.remove(b));
+		assert!(!scene.contains(b));
+		assert!(!scene.contains(a1));
+		assert_eq!(scene.get_node_count(), 2);
+		assert_eq!(scene.iter().count(), 2);
+
+		// reused slots hand out fresh ids, so stale handles stay invalid
+		let c = scene.add(named("c"));
+		assert!(scene.get(a1).is_none() && scene.get(b).is_none());
+		assert_eq!(scene.get_object3d(c).unwrap().get_name(), "c");
+		assert!(!scene.remove(scene.get_root()));
+	}
+}